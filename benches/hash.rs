@@ -0,0 +1,62 @@
+// Compare the average number of probes of each hash table strategy as the load factor grows.
+// Run with `cargo bench --bench hash`.
+use rand::Rng;
+use clrs::hash::{ChainedHashTable, HashFamily, OpenAddressingHashTable, Probing};
+
+const SLOTS: usize = 1 << 16;
+
+fn main() {
+    let mut rng = rand::rng();
+    let families = [HashFamily::Division, HashFamily::Multiplication, HashFamily::Universal];
+    let probings = [Probing::Linear, Probing::Quadratic, Probing::Double];
+    let load_factors = [0.25, 0.5, 0.75, 0.9];
+
+    // Random keys, and keys in arithmetic progression, which the division method maps to runs of adjacent slots.
+    let random: Vec<u64> = (0..SLOTS).map(|_| rng.random()).collect();
+    let strided: Vec<u64> = (0..SLOTS as u64).map(|k| k << 16).collect();
+
+    for (keys_name, keys) in [("random keys", &random), ("strided keys", &strided)] {
+        println!("{keys_name}: average probes of unsuccessful searches at load factor {load_factors:?}");
+        for family in families {
+            let mut row = Vec::new();
+            for &alpha in &load_factors {
+                let mut t = ChainedHashTable::with_slots(family, SLOTS);
+                t.set_max_load_factor(f64::INFINITY);
+                let n = (alpha * t.slots() as f64) as usize;
+                for &k in &keys[..n] {
+                    t.insert(k, ());
+                }
+                t.reset_stats();
+                for k in 0..1000u64 {
+                    t.get(&(k << 40 | 1));
+                }
+                row.push(t.stats().average_probes());
+            }
+            println!("  {:<44} {row:.2?}", format!("chaining, {family:?}"));
+        }
+        for family in families {
+            for probing in probings {
+                let mut row = Vec::new();
+                for &alpha in &load_factors {
+                    let mut t = OpenAddressingHashTable::with_slots(family, probing, SLOTS);
+                    let quadratic_prime = probing == Probing::Quadratic && family == HashFamily::Division;
+                    if quadratic_prime && alpha > 0.5 {
+                        row.push(f64::NAN);
+                        continue;
+                    }
+                    t.set_max_load_factor(alpha.max(0.5));
+                    let n = (alpha * t.slots() as f64) as usize;
+                    for &k in &keys[..n] {
+                        t.insert(k, ());
+                    }
+                    t.reset_stats();
+                    for k in 0..1000u64 {
+                        t.get(&(k << 40 | 1));
+                    }
+                    row.push(t.stats().average_probes());
+                }
+                println!("  {:<44} {row:.2?}", format!("open addressing, {family:?}, {probing:?}"));
+            }
+        }
+    }
+}
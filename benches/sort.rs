@@ -0,0 +1,56 @@
+// Compare `introsort` with `slice::sort_unstable` and the sorts it is built from.
+// Run with `cargo bench --bench sort`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use rand::Rng;
+use clrs::sort;
+
+const ROUNDS: u32 = 5;
+
+type Sort = fn(&mut [u32]);
+
+fn time(sort: Sort, input: &[u32]) -> Duration {
+    let mut best = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let mut a = input.to_vec();
+        let start = Instant::now();
+        sort(black_box(&mut a));
+        best = best.min(start.elapsed());
+        assert!(a.is_sorted());
+    }
+    best
+}
+
+fn main() {
+    let mut rng = rand::rng();
+    let sorts: [(&str, Sort); 4] = [
+        ("sort_unstable", |a| a.sort_unstable()),
+        ("introsort", sort::introsort),
+        ("randomized_quicksort", sort::randomized_quicksort),
+        ("heapsort", sort::heapsort),
+    ];
+
+    for n in [1_000, 100_000, 1_000_000] {
+        let inputs: [(&str, Vec<u32>); 5] = [
+            ("random", (0..n).map(|_| rng.random()).collect()),
+            ("sorted", (0..n).collect()),
+            ("reversed", (0..n).rev().collect()),
+            ("all equal", vec![0; n as usize]),
+            ("4 distinct", (0..n).map(|_| rng.random_range(0..4)).collect()),
+        ];
+        println!("n = {n}");
+        for (input_name, input) in &inputs {
+            print!("  {input_name:<12}");
+            for (sort_name, sort) in sorts {
+                // Lomuto partitioning is quadratic in the number of equal keys.
+                let duplicates = matches!(*input_name, "all equal" | "4 distinct");
+                if sort_name == "randomized_quicksort" && duplicates && n > 1_000 {
+                    print!("  {sort_name}: {:>12}", "skipped");
+                    continue;
+                }
+                print!("  {sort_name}: {:>12?}", time(sort, input));
+            }
+            println!();
+        }
+    }
+}
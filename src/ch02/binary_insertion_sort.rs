@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+// Insertion sort that finds where to insert each key by binary search on the sorted subarray (Exercise 2.3-6),
+// which takes Θ(n lg n) comparisons but still Θ(n^2) moves in the worst case.
+pub fn binary_insertion_sort<T: PartialOrd>(a: &mut [T]) {
+    binary_insertion_sort_by(a, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn binary_insertion_sort_by<T, F>(a: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in 1..a.len() {
+        // Find the first `j` in `a[0..i]` such that `a[j] > a[i]`, so the key goes after equal elements.
+        let mut low = 0;
+        let mut high = i;
+        while low < high {
+            let mid = (low + high) / 2;
+            if compare(&a[mid], &a[i]) == Ordering::Greater {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        a[low..=i].rotate_right(1);
+    }
+}
+
+pub fn binary_insertion_sort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    binary_insertion_sort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn binary_insertion_sort_test() {
+        ch02::tests::sort_i32(binary_insertion_sort);
+        ch02::tests::sort_f64(binary_insertion_sort);
+        ch02::tests::sort_char(binary_insertion_sort);
+        ch02::tests::sort_str(|a| binary_insertion_sort(a));
+        ch02::tests::sort_string(binary_insertion_sort);
+    }
+
+    #[test]
+    fn binary_insertion_sort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| binary_insertion_sort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| binary_insertion_sort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| binary_insertion_sort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| binary_insertion_sort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| binary_insertion_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn binary_insertion_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| binary_insertion_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn binary_insertion_sort_by_panic_test() {
+        ch02::tests::sort_by_panic_permutes(|a, compare| binary_insertion_sort_by(a, compare));
+    }
+}
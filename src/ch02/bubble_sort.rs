@@ -1,18 +1,48 @@
-pub fn bubble_sort<T: PartialOrd + Copy>(a: &mut [T]) {
+use std::cmp::Ordering;
+use crate::utils;
+use crate::utils::counted::MoveCounter;
+
+pub fn bubble_sort<T: PartialOrd>(a: &mut [T]) {
+    bubble_sort_by(a, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn bubble_sort_by<T, F>(a: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    bubble_sort_aux(a, &mut compare, &mut ());
+}
+
+// Bubble sort, reporting each swap to `moves`.
+pub(crate) fn bubble_sort_aux<T, F, M>(a: &mut [T], compare: &mut F, moves: &mut M)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    M: MoveCounter,
+{
     for i in (1..a.len()).rev() {
         let mut swapped = false;
         for j in 0..i {
-            if a[j] > a[j + 1] {
-                (a[j], a[j + 1]) = (a[j + 1], a[j]);
+            if compare(&a[j], &a[j + 1]) == Ordering::Greater {
+                a.swap(j, j + 1);
+                moves.record(2);
                 swapped = true;
             }
         }
-        if swapped == false {
+        if !swapped {
             break;
         }
     }
 }
 
+pub fn bubble_sort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    bubble_sort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ch02;
@@ -24,5 +54,25 @@ mod tests {
         ch02::tests::sort_f64(bubble_sort);
         ch02::tests::sort_char(bubble_sort);
         ch02::tests::sort_str(|a| bubble_sort(a));
+        ch02::tests::sort_string(bubble_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bubble_sort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| bubble_sort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| bubble_sort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| bubble_sort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| bubble_sort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| bubble_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn bubble_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| bubble_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn bubble_sort_by_panic_test() {
+        ch02::tests::sort_by_panic_permutes(|a, compare| bubble_sort_by(a, compare));
+    }
+}
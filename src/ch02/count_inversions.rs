@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use crate::ch02::merge_sort::merge_sort_aux;
+use crate::utils;
+
+// Count the inversions in `a`, i.e. the pairs `(i, j)` such that `i < j` and `a[i] > a[j]`,
+// in Θ(n lg n) time by merge sorting a copy of `a` (Problem 2-4).
+pub fn count_inversions<T: PartialOrd + Clone>(a: &[T]) -> u64 {
+    count_inversions_by(a, utils::partial_compare)
+}
+
+pub fn count_inversions_by<T, F>(a: &[T], mut compare: F) -> u64
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut a = a.to_vec();
+    sort_and_count_inversions(&mut a, &mut compare)
+}
+
+fn sort_and_count_inversions<T, F>(a: &mut [T], compare: &mut F) -> u64
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if a.is_empty() { return 0; }
+    let mut scratch = Vec::with_capacity(a.len().div_ceil(2));
+    merge_sort_aux(a, 0, a.len() - 1, 1, &mut scratch, compare, &mut ())
+}
+
+// Count the pairs of equal elements in the sorted slice `a`.
+fn count_ties<T, F>(a: &[T], compare: &mut F) -> u64
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut ties = 0;
+    let mut run = 1;
+    for i in 1..=a.len() {
+        if i < a.len() && compare(&a[i - 1], &a[i]) == Ordering::Equal {
+            run += 1;
+        } else {
+            ties += run * (run - 1) / 2;
+            run = 1;
+        }
+    }
+    ties
+}
+
+/*
+    Kendall Rank Correlation (tau-b)
+    Compare the rankings `a` and `b` of the same n items, where `a[i]` and `b[i]` are the ranks of the i-th item,
+    in Θ(n lg n) time by Knight's algorithm:
+    sort the items by `(a[i], b[i])`, then the discordant pairs are exactly the inversions in the resulting `b`.
+
+    n0 = n(n - 1) / 2
+    n1 = pairs tied in `a`
+    n2 = pairs tied in `b`
+    n3 = pairs tied in both `a` and `b`
+    nd = discordant pairs
+    tau = (n0 - n1 - n2 + n3 - 2 * nd) / sqrt((n0 - n1) * (n0 - n2))
+
+    Return 1.0 for identical rankings, -1.0 for reversed rankings,
+    and NaN if either ranking has all items tied (including when n < 2).
+*/
+pub fn kendall_tau<T, U>(a: &[T], b: &[U]) -> f64
+where
+    T: PartialOrd + Clone,
+    U: PartialOrd + Clone,
+{
+    assert_eq!(a.len(), b.len(), "mismatched length");
+    let compare_a = utils::partial_compare;
+    let mut compare_b = utils::partial_compare;
+
+    let mut pairs: Vec<(T, U)> = a.iter().cloned().zip(b.iter().cloned()).collect();
+    sort_and_count_inversions(&mut pairs, &mut |x: &(T, U), y: &(T, U)| {
+        compare_a(&x.0, &y.0).then_with(|| compare_b(&x.1, &y.1))
+    });
+    let n1 = count_ties(&pairs, &mut |x: &(T, U), y: &(T, U)| compare_a(&x.0, &y.0));
+    let n3 = count_ties(&pairs, &mut |x: &(T, U), y: &(T, U)| {
+        compare_a(&x.0, &y.0).then_with(|| compare_b(&x.1, &y.1))
+    });
+
+    let mut b: Vec<U> = pairs.into_iter().map(|(_, y)| y).collect();
+    let nd = sort_and_count_inversions(&mut b, &mut compare_b);
+    let n2 = count_ties(&b, &mut compare_b);
+
+    let n = a.len() as u64;
+    let n0 = n * n.saturating_sub(1) / 2;
+    let numerator = (n0 + n3) as f64 - (n1 + n2 + 2 * nd) as f64;
+    let denominator = (((n0 - n1) as f64) * ((n0 - n2) as f64)).sqrt();
+    if denominator == 0.0 { f64::NAN } else { numerator / denominator }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    fn count_inversions_naive(a: &[i32]) -> u64 {
+        let mut inversions = 0;
+        for i in 0..a.len() {
+            for j in i + 1..a.len() {
+                if a[i] > a[j] {
+                    inversions += 1;
+                }
+            }
+        }
+        inversions
+    }
+
+    #[test]
+    fn count_inversions_test() {
+        let cases: [(&[i32], u64); 7] = [
+            (&[], 0),
+            (&[1], 0),
+            (&[1, 2, 3, 4, 5, 6], 0),
+            (&[6, 5, 4, 3, 2, 1], 15),
+            (&[2, 3, 8, 6, 1], 5), // Problem 2-4 (a)
+            (&[2, 2, 1, 1], 4),
+            (&[3, 3, 3], 0),
+        ];
+        for (a, expected) in cases {
+            assert_eq!(count_inversions(a), expected, "{a:?}");
+        }
+
+        let mut rng = rand::rng();
+        for n in [10, 100, 500] {
+            let a: Vec<i32> = (0..n).map(|_| rng.random_range(0..n / 2)).collect();
+            assert_eq!(count_inversions(&a), count_inversions_naive(&a));
+        }
+
+        assert_eq!(count_inversions_by(&[1, 2, 3, 4], |x: &i32, y: &i32| y.cmp(x)), 6);
+        assert_eq!(count_inversions(&["b", "a", "c"]), 1);
+    }
+
+    #[test]
+    fn kendall_tau_test() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(kendall_tau(&a, &[1, 2, 3, 4, 5]), 1.0);
+        assert_eq!(kendall_tau(&a, &[5, 4, 3, 2, 1]), -1.0);
+        assert_eq!(kendall_tau(&a, &[3, 1, 2, 5, 4]), 0.4); // (7 - 3) / 10
+        assert_eq!(kendall_tau(&['a', 'b', 'c', 'd'], &[0.1, 0.4, 0.2, 0.3]), 1.0 / 3.0);
+
+        // tau-b with ties: concordant 3, discordant 1, n0 = 6, n1 = 1, n2 = 1
+        let tau = kendall_tau(&[1, 1, 2, 3], &[1, 3, 2, 3]);
+        assert!((tau - 2.0 / 5.0).abs() < 1e-12, "{tau}");
+
+        assert!(kendall_tau::<i32, i32>(&[], &[]).is_nan());
+        assert!(kendall_tau(&[1], &[1]).is_nan());
+        assert!(kendall_tau(&[1, 1, 1], &[1, 2, 3]).is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatched length")]
+    fn kendall_tau_error() {
+        kendall_tau(&[1, 2], &[1, 2, 3]);
+    }
+}
@@ -1,13 +1,48 @@
-pub fn insertion_sort<T: PartialOrd + Copy>(a: &mut [T]) {
+use std::cmp::Ordering;
+use crate::utils;
+use crate::utils::counted::MoveCounter;
+
+pub fn insertion_sort<T: PartialOrd>(a: &mut [T]) {
+    insertion_sort_by(a, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn insertion_sort_by<T, F>(a: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    insertion_sort_aux(a, &mut compare, &mut ());
+}
+
+// Sort `a` and return the number of shifts, which equals the number of inversions in `a` (Problem 2-4).
+// Each rotation into place is reported to `moves`.
+pub(crate) fn insertion_sort_aux<T, F, M>(a: &mut [T], compare: &mut F, moves: &mut M) -> u64
+where
+    F: FnMut(&T, &T) -> Ordering,
+    M: MoveCounter,
+{
+    let mut shifts = 0;
     for i in 1..a.len() {
-        let key = a[i];
         let mut j = i;
-        while j > 0 && a[j - 1] > key  {
-            a[j] = a[j - 1];
+        while j > 0 && compare(&a[j - 1], &a[i]) == Ordering::Greater {
             j -= 1;
         }
-        a[j] = key;
+        // Shift `a[j..i]` one position to the right and put the key `a[i]` into `a[j]`.
+        a[j..=i].rotate_right(1);
+        if j < i {
+            moves.record(i - j + 1);
+        }
+        shifts += (i - j) as u64;
     }
+    shifts
+}
+
+pub fn insertion_sort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    insertion_sort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
 }
 
 #[cfg(test)]
@@ -21,5 +56,25 @@ mod tests {
         ch02::tests::sort_f64(insertion_sort);
         ch02::tests::sort_char(insertion_sort);
         ch02::tests::sort_str(|a| insertion_sort(a));
+        ch02::tests::sort_string(insertion_sort);
+    }
+
+    #[test]
+    fn insertion_sort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| insertion_sort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| insertion_sort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| insertion_sort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| insertion_sort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| insertion_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn insertion_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| insertion_sort_by_key(a, key));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn insertion_sort_by_panic_test() {
+        ch02::tests::sort_by_panic_permutes(|a, compare| insertion_sort_by(a, compare));
+    }
+}
@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+use crate::ch02::merge_sort::merge_sort_aux;
+use crate::utils;
+
+// Merge sort that hands subarrays of at most `k` elements to `insertion_sort` (Problem 2-1).
+// Insertion sort is faster than merge sort on small inputs, so a `k` in the tens usually beats plain merge sort (`k == 1`).
+pub fn merge_insertion_sort<T: PartialOrd + Clone>(a: &mut [T], k: usize) {
+    merge_insertion_sort_by(a, k, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn merge_insertion_sort_by<T, F>(a: &mut [T], k: usize, mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if a.is_empty() { return; }
+    let mut scratch = Vec::with_capacity(a.len().div_ceil(2));
+    merge_sort_aux(a, 0, a.len() - 1, k.max(1), &mut scratch, &mut compare, &mut ());
+}
+
+pub fn merge_insertion_sort_by_key<T, K, F>(a: &mut [T], k: usize, mut key: F)
+where
+    T: Clone,
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    merge_insertion_sort_by(a, k, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+const CUTOFF_CANDIDATES: [usize; 10] = [1, 2, 4, 8, 12, 16, 24, 32, 48, 64];
+const CUTOFF_ROUNDS: usize = 5;
+
+// Pick the `k` for which `merge_insertion_sort` sorts `sample` fastest on the current machine,
+// taking the best of several timed rounds for each candidate `k`.
+// `sample` should be an unsorted input of the element type and size to be sorted, e.g. a few thousand elements.
+pub fn merge_insertion_sort_cutoff<T: PartialOrd + Clone>(sample: &[T]) -> usize {
+    let mut best_k = 1;
+    let mut best_time = Duration::MAX;
+    for k in CUTOFF_CANDIDATES {
+        let mut time = Duration::MAX;
+        for _ in 0..CUTOFF_ROUNDS {
+            let mut a = sample.to_vec();
+            let start = Instant::now();
+            merge_insertion_sort(&mut a, k);
+            time = time.min(start.elapsed());
+        }
+        if time < best_time {
+            best_k = k;
+            best_time = time;
+        }
+    }
+    best_k
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn merge_insertion_sort_test() {
+        ch02::tests::sort_i32(|a| merge_insertion_sort(a, 0));
+        ch02::tests::sort_i32(|a| merge_insertion_sort(a, 1));
+        ch02::tests::sort_i32(|a| merge_insertion_sort(a, 3));
+        ch02::tests::sort_i32(|a| merge_insertion_sort(a, 100));
+        ch02::tests::sort_f64(|a| merge_insertion_sort(a, 2));
+        ch02::tests::sort_char(|a| merge_insertion_sort(a, 3));
+        ch02::tests::sort_str(|a| merge_insertion_sort(a, 4));
+        ch02::tests::sort_string(|a| merge_insertion_sort(a, 2));
+    }
+
+    #[test]
+    fn merge_insertion_sort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| merge_insertion_sort_by(a, 2, compare));
+        ch02::tests::sort_by_key_i32(|a, key| merge_insertion_sort_by_key(a, 3, key));
+        ch02::tests::sort_by_key_pair(|a, key| merge_insertion_sort_by_key(a, 2, key));
+        ch02::tests::sort_by_struct(|a, compare| merge_insertion_sort_by(a, 2, compare));
+        ch02::tests::sort_by_key_struct(|a, key| merge_insertion_sort_by_key(a, 2, key));
+    }
+
+    #[test]
+    fn merge_insertion_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| merge_insertion_sort_by_key(a, 1, key));
+        ch02::tests::sort_by_key_stable(|a, key| merge_insertion_sort_by_key(a, 7, key));
+        ch02::tests::sort_by_key_stable(|a, key| merge_insertion_sort_by_key(a, 32, key));
+    }
+
+    #[test]
+    fn merge_insertion_sort_cutoff_test() {
+        let mut rng = rand::rng();
+        let sample: Vec<u64> = (0..1000).map(|_| rng.random()).collect();
+        let k = merge_insertion_sort_cutoff(&sample);
+        assert!(CUTOFF_CANDIDATES.contains(&k));
+        println!("# merge_insertion_sort_cutoff_test: k: {k}");
+
+        let mut a = sample.clone();
+        merge_insertion_sort(&mut a, k);
+        assert!(a.is_sorted());
+    }
+}
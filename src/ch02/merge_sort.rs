@@ -1,51 +1,143 @@
-pub fn merge_sort<T: PartialOrd + Copy>(a: &mut [T]) {
-    if a.len() == 0 { return; }
-    merge_sort_aux(a, 0, a.len() - 1);
+use std::cmp::Ordering;
+use std::mem;
+use crate::ch02::insertion_sort::insertion_sort_aux;
+use crate::utils;
+use crate::utils::counted::MoveCounter;
+
+pub fn merge_sort<T: PartialOrd + Clone>(a: &mut [T]) {
+    merge_sort_by(a, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+// Unlike `insertion_sort` and `bubble_sort`, which sort by swapping elements, `merge_sort` clones each left subarray
+// into an auxiliary buffer before merging. If `compare` panics, `a` may be left with some elements duplicated or lost.
+pub fn merge_sort_by<T, F>(a: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    merge_sort_with_buffer_by(a, &mut Vec::with_capacity(a.len().div_ceil(2)), compare);
+}
+
+pub fn merge_sort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    T: Clone,
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    merge_sort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
 }
 
-fn merge_sort_aux<T: PartialOrd + Copy>(a: &mut [T], p: usize, r: usize) {
-    if p >= r { return; }
+// Sort `a` using `scratch` as the auxiliary buffer, which allocates nothing
+// if the capacity of `scratch` is already at least `⌈a.len() / 2⌉`. `scratch` is left empty.
+pub fn merge_sort_with_buffer<T: PartialOrd + Clone>(a: &mut [T], scratch: &mut Vec<T>) {
+    merge_sort_with_buffer_by(a, scratch, utils::partial_compare);
+}
+
+pub fn merge_sort_with_buffer_by<T, F>(a: &mut [T], scratch: &mut Vec<T>, mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if a.is_empty() { return; }
+    scratch.clear();
+    scratch.reserve(a.len().div_ceil(2));
+    merge_sort_aux(a, 0, a.len() - 1, 1, scratch, &mut compare, &mut ());
+    scratch.clear();
+}
+
+// Bottom-up merge sort, which merges runs of width 1, 2, 4, ... iteratively instead of recursing.
+pub fn merge_sort_bottom_up<T: PartialOrd + Clone>(a: &mut [T]) {
+    merge_sort_bottom_up_by(a, utils::partial_compare);
+}
+
+pub fn merge_sort_bottom_up_by<T, F>(a: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = a.len();
+    // The longest left run is the largest power of 2 less than `n`.
+    let mut scratch = Vec::with_capacity(n.next_power_of_two() / 2);
+    let mut width = 1;
+    while width < n {
+        let mut p = 0;
+        while p + width < n {
+            let q = p + width - 1;
+            let r = (p + 2 * width - 1).min(n - 1);
+            merge(a, p, q, r, &mut scratch, &mut compare, &mut ());
+            p += 2 * width;
+        }
+        width *= 2;
+    }
+}
+
+// Sort `a[p..=r]`, handing subarrays of at most `k` elements to `insertion_sort`,
+// and return the number of inversions in `a[p..=r]`. Plain merge sort is the case `k == 1`.
+// Every element moved is reported to `moves`.
+pub(crate) fn merge_sort_aux<T, F, M>(a: &mut [T], p: usize, r: usize, k: usize, scratch: &mut Vec<T>, compare: &mut F, moves: &mut M) -> u64
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    M: MoveCounter,
+{
+    if r - p < k {
+        return if k > 1 { insertion_sort_aux(&mut a[p..=r], compare, moves) } else { 0 };
+    }
     let q = (p + r) / 2;
-    merge_sort_aux(a, p, q);
-    merge_sort_aux(a, q + 1, r);
-    merge(a, p, q, r);
+    merge_sort_aux(a, p, q, k, scratch, compare, moves)
+        + merge_sort_aux(a, q + 1, r, k, scratch, compare, moves)
+        + merge(a, p, q, r, scratch, compare, moves)
 }
 
-fn merge<T: PartialOrd + Copy>(a: &mut [T], p: usize, q: usize, r: usize) {
-    let left = Vec::from(&a[p..=q]);
-    let right = Vec::from(&a[q + 1..=r]);
+// Merge the sorted runs `a[p..=q]` and `a[q + 1..=r]`, where only the left run is cloned into `scratch`,
+// and return the number of inversions between the runs: each element taken from the right run
+// is inverted with every element remaining in the left run (Problem 2-4).
+// Since `k < j` holds until the left run is exhausted, `a[k]` never overwrites an unmerged element of the right run,
+// and once the left run is exhausted the rest of the right run is already in place.
+fn merge<T, F, M>(a: &mut [T], p: usize, q: usize, r: usize, scratch: &mut Vec<T>, compare: &mut F, moves: &mut M) -> u64
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    M: MoveCounter,
+{
+    scratch.clear();
+    scratch.extend_from_slice(&a[p..=q]);
 
-    let n_left = left.len();
-    let n_right = right.len();
+    // `scratch` holds clones that are dropped by the next merge,
+    // so their elements are moved back into `a` by swapping rather than cloned again.
+    let n_left = scratch.len();
     let mut i = 0;
-    let mut j = 0;
+    let mut j = q + 1;
     let mut k = p;
-    while i < n_left && j < n_right {
-        if left[i] < right[j] {
-            a[k] = left[i];
+    let mut inversions = 0;
+    while i < n_left && j <= r {
+        // Take from the left run on ties so that equal elements keep their relative order.
+        if compare(&scratch[i], &a[j]) != Ordering::Greater {
+            mem::swap(&mut a[k], &mut scratch[i]);
             i += 1;
         } else {
-            a[k] = right[j];
+            a.swap(k, j);
             j += 1;
+            inversions += (n_left - i) as u64;
         }
         k += 1;
     }
 
     while i < n_left {
-        a[k] = left[i];
+        mem::swap(&mut a[k], &mut scratch[i]);
         i += 1;
         k += 1;
     }
-
-    while j < n_right {
-        a[k] = right[j];
-        j += 1;
-        k += 1;
-    }    
+    // The clones into `scratch`, and a swap for each element put into `a[p..k]`.
+    moves.record(n_left + 2 * (k - p));
+    inversions
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::Rng;
+    use rand::seq::SliceRandom;
     use crate::ch02;
     use super::*;
 
@@ -55,5 +147,67 @@ mod tests {
         ch02::tests::sort_f64(merge_sort);
         ch02::tests::sort_char(merge_sort);
         ch02::tests::sort_str(|a| merge_sort(a));
+        ch02::tests::sort_string(merge_sort);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn merge_sort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| merge_sort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| merge_sort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| merge_sort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| merge_sort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| merge_sort_by_key(a, key));
+    }
+
+    #[test]
+    fn merge_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| merge_sort_by_key(a, key));
+        ch02::tests::sort_by_key_stable(|a, key| merge_sort_with_buffer_by(a, &mut Vec::new(), |x, y| key(x).cmp(&key(y))));
+        ch02::tests::sort_by_key_stable(|a, key| merge_sort_bottom_up_by(a, |x, y| key(x).cmp(&key(y))));
+    }
+
+    #[test]
+    fn merge_sort_with_buffer_test() {
+        ch02::tests::sort_i32(|a| merge_sort_with_buffer(a, &mut Vec::new()));
+        ch02::tests::sort_f64(|a| merge_sort_with_buffer(a, &mut Vec::new()));
+        ch02::tests::sort_string(|a| merge_sort_with_buffer(a, &mut Vec::new()));
+        ch02::tests::sort_by_i32(|a, compare| merge_sort_with_buffer_by(a, &mut Vec::new(), compare));
+        ch02::tests::sort_by_struct(|a, compare| merge_sort_with_buffer_by(a, &mut Vec::new(), compare));
+
+        let mut rng = rand::rng();
+        let mut scratch = Vec::with_capacity(50);
+        let capacity = scratch.capacity();
+        for _ in 0..10 {
+            let mut a: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+            a.shuffle(&mut rng);
+            merge_sort_with_buffer(&mut a, &mut scratch);
+            assert!(a.is_sorted());
+            assert!(scratch.is_empty());
+            assert_eq!(scratch.capacity(), capacity);
+        }
+    }
+
+    #[test]
+    fn merge_sort_bottom_up_test() {
+        ch02::tests::sort_i32(merge_sort_bottom_up);
+        ch02::tests::sort_f64(merge_sort_bottom_up);
+        ch02::tests::sort_char(merge_sort_bottom_up);
+        ch02::tests::sort_str(|a| merge_sort_bottom_up(a));
+        ch02::tests::sort_string(merge_sort_bottom_up);
+        ch02::tests::sort_by_i32(|a, compare| merge_sort_bottom_up_by(a, compare));
+        ch02::tests::sort_by_struct(|a, compare| merge_sort_bottom_up_by(a, compare));
+
+        let mut rng = rand::rng();
+        let mut a: Vec<u32> = (0..1 << 16).map(|_| rng.random()).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        merge_sort_bottom_up(&mut a);
+        assert_eq!(a, expected);
+    }
+
+    #[test]
+    fn merge_sort_by_panic_test() {
+        ch02::tests::sort_by_panic_clones(|a, compare| merge_sort_by(a, compare));
+        ch02::tests::sort_by_panic_clones(|a, compare| merge_sort_bottom_up_by(a, compare));
+    }
+}
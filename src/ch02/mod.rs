@@ -1,15 +1,27 @@
 mod insertion_sort;
+mod binary_insertion_sort;
 mod merge_sort;
+mod merge_insertion_sort;
 mod bubble_sort;
+mod count_inversions;
 
 pub use insertion_sort::*;
+pub use binary_insertion_sort::*;
 pub use merge_sort::*;
+pub use merge_insertion_sort::*;
 pub use bubble_sort::*;
+pub use count_inversions::*;
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use std::cell::Cell;
+    use std::cmp::Ordering;
+    use std::panic::{self, AssertUnwindSafe};
     use rand::seq::SliceRandom;
 
+    pub type SortBy<T> = fn(&mut [T], fn(&T, &T) -> Ordering);
+    pub type SortByKey<T, K> = fn(&mut [T], fn(&T) -> K);
+
     pub fn sort_i32(sort: fn(&mut [i32])) {
         let cases: [(&mut [i32], &[i32]); 7] = [
             (&mut [], &[]),
@@ -49,4 +61,167 @@ mod tests {
         sort(&mut a);
         assert_eq!(a, ["a", "b", "b", "c", "d", "e", "ee", "f"]);
     }
+
+    pub fn sort_by_i32(sort_by: SortBy<i32>) {
+        let cases: [(&mut [i32], &[i32]); 5] = [
+            (&mut [], &[]),
+            (&mut [1], &[1]),
+            (&mut [1, 2, 3, 4, 5, 6], &[6, 5, 4, 3, 2, 1]),
+            (&mut [5, 2, 4, 6, 1, 3], &[6, 5, 4, 3, 2, 1]),
+            (&mut [-3, 5, 4, -1, 2, 2, -6], &[5, 4, 2, 2, -1, -3, -6]),
+        ];
+        for (a, expected) in cases {
+            sort_by(a, |x, y| y.cmp(x));
+            assert_eq!(a, expected);
+        }
+
+        let mut rng = rand::rng();
+        let mut a = [7, 2, 4, 5, 8, 3, 6, 1];
+        a.shuffle(&mut rng);
+        sort_by(&mut a, |x, y| (x % 2).cmp(&(y % 2)).then(x.cmp(y)));
+        assert_eq!(a, [2, 4, 6, 8, 1, 3, 5, 7]);
+    }
+
+    pub fn sort_by_key_i32(sort_by_key: SortByKey<i32, i32>) {
+        let mut rng = rand::rng();
+        let mut a = [-3, 5, 4, -1, 2, -6];
+        a.shuffle(&mut rng);
+        sort_by_key(&mut a, |x| x.abs());
+        assert_eq!(a, [-1, 2, -3, 4, 5, -6]);
+
+        a.shuffle(&mut rng);
+        sort_by_key(&mut a, |x| -x);
+        assert_eq!(a, [5, 4, 2, -1, -3, -6]);
+    }
+
+    pub fn sort_by_key_pair(sort_by_key: SortByKey<(char, f64), f64>) {
+        let mut rng = rand::rng();
+        let mut a = [('a', 0.5), ('b', -0.1), ('c', 0.3), ('d', 0.0), ('e', -0.7)];
+        a.shuffle(&mut rng);
+        sort_by_key(&mut a, |&(_, score)| score);
+        assert_eq!(a, [('e', -0.7), ('b', -0.1), ('d', 0.0), ('c', 0.3), ('a', 0.5)]);
+    }
+
+    pub fn sort_string(sort: fn(&mut [String])) {
+        let mut rng = rand::rng();
+        let expected = ["", "a", "ab", "b", "b", "ba", "c", "hello", "world"].map(String::from);
+        let mut a = expected.clone();
+        a.shuffle(&mut rng);
+        sort(&mut a);
+        assert_eq!(a, expected);
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Person {
+        pub name: String,
+        pub age: u32,
+    }
+
+    fn people() -> Vec<Person> {
+        [("Alice", 31), ("Bob", 25), ("Carol", 47), ("Dave", 19), ("Eve", 38)]
+            .map(|(name, age)| Person { name: String::from(name), age })
+            .to_vec()
+    }
+
+    pub fn sort_by_key_struct(sort_by_key: SortByKey<Person, u32>) {
+        let mut rng = rand::rng();
+        let mut a = people();
+        a.shuffle(&mut rng);
+        sort_by_key(&mut a, |p| p.age);
+        let names: Vec<_> = a.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Dave", "Bob", "Alice", "Eve", "Carol"]);
+    }
+
+    pub fn sort_by_struct(sort_by: SortBy<Person>) {
+        let mut rng = rand::rng();
+        let mut a = people();
+        a.shuffle(&mut rng);
+        sort_by(&mut a, |p, q| q.name.cmp(&p.name));
+        let names: Vec<_> = a.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Eve", "Dave", "Carol", "Bob", "Alice"]);
+    }
+
+    // Sort `(key, original_index)` pairs by key, and check that pairs with equal keys keep their original order.
+    pub fn sort_by_key_stable(sort_by_key: SortByKey<(u32, usize), u32>) {
+        let mut rng = rand::rng();
+        for (n, keys) in [(0, 1), (1, 1), (10, 1), (10, 2), (100, 3), (100, 10), (257, 16)] {
+            let mut a: Vec<(u32, usize)> = (0..n).map(|i| (i as u32 % keys, 0)).collect();
+            a.shuffle(&mut rng);
+            for (i, pair) in a.iter_mut().enumerate() {
+                pair.1 = i;
+            }
+
+            sort_by_key(&mut a, |&(key, _)| key);
+            for w in a.windows(2) {
+                assert!(w[0].0 <= w[1].0, "not sorted: {w:?}");
+                if w[0].0 == w[1].0 {
+                    assert!(w[0].1 < w[1].1, "not stable: {w:?}");
+                }
+            }
+        }
+    }
+
+    // Inputs that drive naive pivot choices to their worst case: sorted, reversed, all-equal and few distinct keys.
+    pub fn sort_adversarial(sort: fn(&mut [i32])) {
+        let n = 2000;
+        let cases: [Vec<i32>; 5] = [
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            vec![7; n as usize],
+            (0..n).map(|i| i % 3).collect(),
+            (0..n).map(|i| if i % 2 == 0 { i } else { n - i }).collect(),
+        ];
+        for mut a in cases {
+            let mut expected = a.clone();
+            expected.sort();
+            sort(&mut a);
+            assert_eq!(a, expected);
+        }
+    }
+
+    thread_local! {
+        static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    // Sort `a` with a comparator that panics on its `n`-th call, and check that the panic reaches the caller.
+    fn sort_by_panic(sort_by: SortBy<String>, a: &mut [String], n: usize) {
+        COMPARISONS.set(n);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            sort_by(a, |x, y| {
+                let n = COMPARISONS.get() - 1;
+                COMPARISONS.set(n);
+                if n == 0 { panic!("comparator panicked"); }
+                x.cmp(y)
+            })
+        }));
+        assert!(result.is_err(), "comparator did not panic after {n} comparisons");
+    }
+
+    fn strings() -> Vec<String> {
+        (0..16).map(|i| format!("s{:02}", (i * 7) % 16)).collect()
+    }
+
+    // For sorts that only swap elements, `a` must still be a permutation of its original elements after a panic.
+    pub fn sort_by_panic_permutes(sort_by: SortBy<String>) {
+        for n in [1, 5, 20] {
+            let original = strings();
+            let mut a = original.clone();
+            sort_by_panic(sort_by, &mut a, n);
+            let mut expected = original;
+            expected.sort();
+            a.sort();
+            assert_eq!(a, expected);
+        }
+    }
+
+    // For sorts that clone elements, `a` must still hold only valid elements of the original after a panic.
+    pub fn sort_by_panic_clones(sort_by: SortBy<String>) {
+        for n in [1, 5, 20] {
+            let original = strings();
+            let mut a = original.clone();
+            sort_by_panic(sort_by, &mut a, n);
+            assert_eq!(a.len(), original.len());
+            assert!(a.iter().all(|x| original.contains(x)), "{a:?} has an element not in {original:?}");
+        }
+    }
 }
\ No newline at end of file
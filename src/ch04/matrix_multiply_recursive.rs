@@ -25,6 +25,7 @@ where
     c
 }
 
+#[allow(clippy::too_many_arguments)]
 fn matrix_multiply_recursive_aux<T, const N: usize>(
     a: &Mat<T, N, N>,
     b: &Mat<T, N, N>,
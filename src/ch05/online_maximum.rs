@@ -6,15 +6,15 @@ pub fn online_maximum(scores: &[u32]) -> usize {
 
 pub fn online_maximum_aux(k: usize, scores: &[u32]) -> usize {
     let mut best_score = 0;
-    for i in 0..k {
-        if scores[i] > best_score {
-            best_score = scores[i];
+    for &score in &scores[..k] {
+        if score > best_score {
+            best_score = score;
         }
     }
 
     let n = scores.len();
-    for i in k..n {
-        if scores[i] > best_score {
+    for (i, &score) in scores.iter().enumerate().skip(k) {
+        if score > best_score {
             return i;
         }
     }
@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+/*
+    d-ary Heap (Problem 6-2)
+    The node at index i has PARENT(i) = (i - 1) / d and children d * i + 1, ..., d * i + d.
+    A heap of n elements has height Θ(log_d n), so `push` takes O(log_d n) time
+    and `pop` takes O(d log_d n) time, as each level compares the d children.
+*/
+#[derive(Debug, Clone)]
+pub struct DaryHeap<T, const D: usize, F> {
+    data: Vec<T>,
+    compare: F,
+}
+
+impl<T: PartialOrd, const D: usize> DaryHeap<T, D, utils::Compare<T>> {
+    pub fn max() -> Self {
+        DaryHeap::with_compare(utils::partial_compare)
+    }
+
+    pub fn min() -> Self {
+        DaryHeap::with_compare(|x, y| utils::partial_compare(y, x))
+    }
+}
+
+impl<T, const D: usize, F> DaryHeap<T, D, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    pub fn with_compare(compare: F) -> Self {
+        const { assert!(D >= 2, "arity of a d-ary heap must be at least 2") };
+        DaryHeap { data: Vec::new(), compare }
+    }
+
+    // Build a heap from `data` in linear time.
+    pub fn from_vec(data: Vec<T>, compare: F) -> Self {
+        let mut heap = DaryHeap::with_compare(compare);
+        heap.data = data;
+        for i in (0..heap.data.len().div_ceil(D)).rev() {
+            heap.sift_down(i, heap.data.len());
+        }
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.data.push(x);
+        let mut i = self.data.len() - 1;
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if (self.compare)(&self.data[parent], &self.data[i]) != Ordering::Less { break; }
+            self.data.swap(parent, i);
+            i = parent;
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() { return None; }
+        let top = self.data.swap_remove(0);
+        self.sift_down(0, self.data.len());
+        Some(top)
+    }
+
+    // Return the elements in ascending order with respect to `compare`.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for i in (1..self.data.len()).rev() {
+            self.data.swap(0, i);
+            self.sift_down(0, i);
+        }
+        self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    // MAX-HEAPIFY on the heap `data[..n]`, where the largest of up to d children is found by a linear scan.
+    fn sift_down(&mut self, mut i: usize, n: usize) {
+        loop {
+            let first = D * i + 1;
+            let mut largest = i;
+            for child in first..(first + D).min(n) {
+                if (self.compare)(&self.data[child], &self.data[largest]) == Ordering::Greater {
+                    largest = child;
+                }
+            }
+            if largest == i { return; }
+            self.data.swap(i, largest);
+            i = largest;
+        }
+    }
+}
+
+impl<T: PartialOrd, const D: usize> Default for DaryHeap<T, D, utils::Compare<T>> {
+    fn default() -> Self {
+        DaryHeap::max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    fn is_heap<T: PartialOrd, const D: usize, F>(heap: &DaryHeap<T, D, F>) -> bool {
+        (1..heap.data.len()).all(|i| heap.data[(i - 1) / D] >= heap.data[i])
+    }
+
+    fn d_ary_heap_aux<const D: usize>() {
+        let mut rng = rand::rng();
+        let mut heap: DaryHeap<i32, D, _> = DaryHeap::max();
+        let mut expected = Vec::new();
+        for _ in 0..200 {
+            let x = rng.random_range(-100..100);
+            heap.push(x);
+            expected.push(x);
+            assert!(is_heap(&heap));
+        }
+        expected.sort_by(|x, y| y.cmp(x));
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            assert!(is_heap(&heap));
+            popped.push(x);
+        }
+        assert_eq!(popped, expected);
+
+        let heap = DaryHeap::<i32, D, _>::from_vec(expected.clone(), |x: &i32, y: &i32| x.cmp(y));
+        assert!(is_heap(&heap));
+        expected.reverse();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn d_ary_heap_test() {
+        d_ary_heap_aux::<2>();
+        d_ary_heap_aux::<3>();
+        d_ary_heap_aux::<4>();
+        d_ary_heap_aux::<8>();
+    }
+
+    #[test]
+    fn d_ary_min_heap_test() {
+        let mut heap: DaryHeap<&str, 3, _> = DaryHeap::min();
+        assert_eq!(heap.pop(), None);
+        for x in ["pear", "apple", "fig", "banana", "cherry"] {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some(&"apple"));
+        assert_eq!(heap.pop(), Some("apple"));
+        assert_eq!(heap.into_sorted_vec(), ["pear", "fig", "cherry", "banana"]);
+
+        let heap: DaryHeap<i32, 4, _> = DaryHeap::default();
+        assert!(heap.is_empty());
+        assert_eq!(heap.into_vec(), []);
+    }
+}
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+use crate::ch06;
+use crate::utils;
+
+// Binary heap over a `Vec<T>` ordered by `compare`, whose top is the greatest element with respect to `compare`.
+// A max-heap uses the natural order, and a min-heap uses the reverse order.
+#[derive(Debug, Clone)]
+pub struct Heap<T, F> {
+    data: Vec<T>,
+    compare: F,
+}
+
+// Heap ordered by a plain comparison function, which is the type of both `Heap::max` and `Heap::min`.
+// Its `Default` is a max-heap.
+pub type CompareHeap<T> = Heap<T, utils::Compare<T>>;
+
+impl<T: PartialOrd> Heap<T, utils::Compare<T>> {
+    pub fn max() -> Self {
+        Heap::with_compare(utils::partial_compare)
+    }
+
+    pub fn min() -> Self {
+        Heap::with_compare(|x, y| utils::partial_compare(y, x))
+    }
+}
+
+impl<T, F> Heap<T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    pub fn with_compare(compare: F) -> Self {
+        Heap { data: Vec::new(), compare }
+    }
+
+    // Build a heap from `data` in linear time.
+    pub fn from_vec(mut data: Vec<T>, mut compare: F) -> Self {
+        ch06::build_max_heap_by(&mut data, &mut compare);
+        Heap { data, compare }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    // Insert `x` and let it float up to its position (MAX-HEAP-INSERT).
+    pub fn push(&mut self, x: T) {
+        self.data.push(x);
+        let mut i = self.data.len() - 1;
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if (self.compare)(&self.data[parent], &self.data[i]) != Ordering::Less { break; }
+            self.data.swap(parent, i);
+            i = parent;
+        }
+    }
+
+    // Remove and return the top (HEAP-EXTRACT-MAX).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() { return None; }
+        let top = self.data.swap_remove(0);
+        ch06::max_heapify_by(&mut self.data, 0, &mut self.compare);
+        Some(top)
+    }
+
+    // Return the elements in ascending order with respect to `compare`.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for i in (1..self.data.len()).rev() {
+            self.data.swap(0, i);
+            ch06::max_heapify_by(&mut self.data[..i], 0, &mut self.compare);
+        }
+        self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: PartialOrd> Default for Heap<T, utils::Compare<T>> {
+    fn default() -> Self {
+        Heap::max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch06::tests::is_max_heap;
+    use super::*;
+
+    #[test]
+    fn max_heap_test() {
+        let mut heap = Heap::max();
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+
+        for x in [4, 1, 3, 2, 16, 9, 10, 14, 8, 7] {
+            heap.push(x);
+            assert!(is_max_heap(&heap.data));
+        }
+        assert_eq!(heap.len(), 10);
+        assert_eq!(heap.peek(), Some(&16));
+
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            assert!(is_max_heap(&heap.data));
+            popped.push(x);
+        }
+        assert_eq!(popped, [16, 14, 10, 9, 8, 7, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn min_heap_test() {
+        let mut heap = Heap::min();
+        for x in ["pear", "apple", "fig", "banana"] {
+            heap.push(String::from(x));
+        }
+        assert_eq!(heap.peek().map(String::as_str), Some("apple"));
+        assert_eq!(heap.pop().as_deref(), Some("apple"));
+        assert_eq!(heap.pop().as_deref(), Some("banana"));
+        heap.push(String::from("cherry"));
+        assert_eq!(heap.into_sorted_vec(), ["pear", "fig", "cherry"]);
+    }
+
+    #[test]
+    fn heap_with_compare_test() {
+        // Max-heap on the absolute value
+        let mut heap = Heap::with_compare(|x: &i32, y: &i32| x.abs().cmp(&y.abs()));
+        for x in [3, -7, 1, -2, 5] {
+            heap.push(x);
+        }
+        assert_eq!(heap.pop(), Some(-7));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.into_sorted_vec(), [1, -2, 3]);
+
+        let mut rng = rand::rng();
+        let data: Vec<i32> = (0..100).map(|_| rng.random_range(-50..50)).collect();
+        let heap = Heap::from_vec(data.clone(), |x: &i32, y: &i32| x.cmp(y));
+        assert!(is_max_heap(&heap.data));
+        let mut expected = data;
+        expected.sort();
+        assert_eq!(heap.into_sorted_vec(), expected);
+
+        let mut heap: CompareHeap<i32> = Heap::default();
+        assert_eq!(heap.peek(), None);
+        heap.push(1);
+        heap.push(2);
+        assert_eq!(heap.pop(), Some(2));
+    }
+}
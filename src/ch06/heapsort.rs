@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+/*
+    Heaps are stored in 0-based arrays, so the node at index i has
+    PARENT(i) = (i - 1) / 2, LEFT(i) = 2i + 1 and RIGHT(i) = 2i + 2.
+    The whole slice `a` is the heap, i.e. `a.heap-size == a.len()`.
+*/
+pub fn heapsort<T: PartialOrd>(a: &mut [T]) {
+    heapsort_by(a, utils::partial_compare);
+}
+
+pub fn heapsort_by<T, F>(a: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    build_max_heap_by(a, &mut compare);
+    for i in (1..a.len()).rev() {
+        a.swap(0, i);
+        max_heapify_by(&mut a[..i], 0, &mut compare);
+    }
+}
+
+pub fn heapsort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    heapsort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+// Let `a[i]` float down so that the subtree rooted at `i` obeys the max-heap property,
+// assuming the subtrees rooted at `LEFT(i)` and `RIGHT(i)` already do.
+pub fn max_heapify<T: PartialOrd>(a: &mut [T], i: usize) {
+    max_heapify_by(a, i, &mut utils::partial_compare);
+}
+
+pub fn max_heapify_by<T, F>(a: &mut [T], mut i: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = a.len();
+    loop {
+        let l = 2 * i + 1;
+        let r = 2 * i + 2;
+        let mut largest = i;
+        if l < n && compare(&a[l], &a[largest]) == Ordering::Greater {
+            largest = l;
+        }
+        if r < n && compare(&a[r], &a[largest]) == Ordering::Greater {
+            largest = r;
+        }
+        if largest == i { return; }
+        a.swap(i, largest);
+        i = largest;
+    }
+}
+
+pub fn build_max_heap<T: PartialOrd>(a: &mut [T]) {
+    build_max_heap_by(a, &mut utils::partial_compare);
+}
+
+pub fn build_max_heap_by<T, F>(a: &mut [T], compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in (0..a.len() / 2).rev() {
+        max_heapify_by(a, i, compare);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    pub fn is_max_heap<T: PartialOrd>(a: &[T]) -> bool {
+        (1..a.len()).all(|i| a[(i - 1) / 2] >= a[i])
+    }
+
+    #[test]
+    fn max_heapify_test() {
+        // Figure 6.2 in 0-based indices
+        let mut a = [16, 4, 10, 14, 7, 9, 3, 2, 8, 1];
+        max_heapify(&mut a, 1);
+        assert_eq!(a, [16, 14, 10, 8, 7, 9, 3, 2, 4, 1]);
+
+        let mut a = [1];
+        max_heapify(&mut a, 0);
+        assert_eq!(a, [1]);
+    }
+
+    #[test]
+    fn build_max_heap_test() {
+        // Figure 6.3
+        let mut a = [4, 1, 3, 2, 16, 9, 10, 14, 8, 7];
+        build_max_heap(&mut a);
+        assert_eq!(a, [16, 14, 10, 8, 7, 9, 3, 2, 4, 1]);
+
+        let mut rng = rand::rng();
+        for n in [0, 1, 2, 3, 10, 100] {
+            let mut a: Vec<i32> = (0..n).map(|_| rng.random_range(-50..50)).collect();
+            build_max_heap(&mut a);
+            assert!(is_max_heap(&a), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn heapsort_test() {
+        ch02::tests::sort_i32(heapsort);
+        ch02::tests::sort_f64(heapsort);
+        ch02::tests::sort_char(heapsort);
+        ch02::tests::sort_str(|a| heapsort(a));
+        ch02::tests::sort_string(heapsort);
+    }
+
+    #[test]
+    fn heapsort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| heapsort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| heapsort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| heapsort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| heapsort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| heapsort_by_key(a, key));
+    }
+
+    #[test]
+    fn heapsort_by_panic_test() {
+        ch02::tests::sort_by_panic_permutes(|a, compare| heapsort_by(a, compare));
+    }
+}
@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::mem;
+use crate::ch06;
+use crate::utils;
+
+// Iterator that lazily merges k sorted iterators into one sorted sequence in O(lg k) time per element (Exercise 6.5-11),
+// keeping a min-heap of the current head of each iterator.
+// The merge is stable: equal elements come out in the order of their iterators, and in order within each iterator.
+pub struct KWayMerge<I: Iterator, F> {
+    iters: Vec<I>,
+    heap: Vec<(I::Item, usize)>, // (head, index of its iterator)
+    compare: F,
+}
+
+pub fn k_way_merge<I, J, T>(iters: I) -> KWayMerge<J::IntoIter, utils::Compare<T>>
+where
+    I: IntoIterator<Item = J>,
+    J: IntoIterator<Item = T>,
+    T: PartialOrd,
+{
+    k_way_merge_by(iters, utils::partial_compare)
+}
+
+pub fn k_way_merge_by<I, J, T, F>(iters: I, mut compare: F) -> KWayMerge<J::IntoIter, F>
+where
+    I: IntoIterator<Item = J>,
+    J: IntoIterator<Item = T>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut iters: Vec<_> = iters.into_iter().map(IntoIterator::into_iter).collect();
+    let mut heap: Vec<_> = iters.iter_mut().enumerate().filter_map(|(i, iter)| Some((iter.next()?, i))).collect();
+    ch06::build_max_heap_by(&mut heap, &mut |x, y| min_first(&mut compare, x, y));
+    KWayMerge { iters, heap, compare }
+}
+
+pub fn k_way_merge_by_key<I, J, T, K, G>(iters: I, mut key: G) -> KWayMerge<J::IntoIter, impl FnMut(&T, &T) -> Ordering>
+where
+    I: IntoIterator<Item = J>,
+    J: IntoIterator<Item = T>,
+    K: PartialOrd,
+    G: FnMut(&T) -> K,
+{
+    k_way_merge_by(iters, move |x, y| utils::partial_compare(&key(x), &key(y)))
+}
+
+// Order the heap entries so that the max-heap top is the smallest head, taking the earliest iterator on ties.
+fn min_first<T, F>(compare: &mut F, x: &(T, usize), y: &(T, usize)) -> Ordering
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    compare(&y.0, &x.0).then(y.1.cmp(&x.1))
+}
+
+impl<I, F> Iterator for KWayMerge<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &(_, i) = self.heap.first()?;
+        // Replace the smallest head by the next element of its iterator, or drop its entry if the iterator is exhausted.
+        let item = match self.iters[i].next() {
+            Some(next) => mem::replace(&mut self.heap[0].0, next),
+            None => self.heap.swap_remove(0).0,
+        };
+        let compare = &mut self.compare;
+        ch06::max_heapify_by(&mut self.heap, 0, &mut |x, y| min_first(compare, x, y));
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.heap.len();
+        self.iters.iter().fold((n, Some(n)), |(low, high), iter| {
+            let (l, h) = iter.size_hint();
+            (low.saturating_add(l), high.zip(h).and_then(|(high, h)| high.checked_add(h)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn k_way_merge_test() {
+        let merged: Vec<i32> = k_way_merge([vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]).collect();
+        assert_eq!(merged, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let merged: Vec<i32> = k_way_merge([vec![], vec![5], vec![], vec![1, 2, 10]]).collect();
+        assert_eq!(merged, [1, 2, 5, 10]);
+
+        let merged: Vec<i32> = k_way_merge(Vec::<Vec<i32>>::new()).collect();
+        assert_eq!(merged, []);
+
+        let merged: Vec<&str> = k_way_merge([["a", "c"].iter().copied(), ["b", "d"].iter().copied()]).collect();
+        assert_eq!(merged, ["a", "b", "c", "d"]);
+
+        let mut rng = rand::rng();
+        let lists: Vec<Vec<u32>> = (0..20)
+            .map(|_| {
+                let mut list: Vec<u32> = (0..rng.random_range(0..50)).map(|_| rng.random_range(0..100)).collect();
+                list.sort();
+                list
+            })
+            .collect();
+        let mut expected: Vec<u32> = lists.concat();
+        expected.sort();
+        let merge = k_way_merge(lists);
+        assert_eq!(merge.size_hint(), (expected.len(), Some(expected.len())));
+        assert_eq!(merge.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn k_way_merge_lazy_test() {
+        // Infinite iterators are merged lazily.
+        let evens = (0..).step_by(2);
+        let odds = (1..).step_by(2);
+        let merged: Vec<u64> = k_way_merge([evens, odds]).take(6).collect();
+        assert_eq!(merged, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn k_way_merge_by_test() {
+        let merged: Vec<i32> = k_way_merge_by([vec![9, 5, 1], vec![8, 2], vec![7, 3]], |x, y| y.cmp(x)).collect();
+        assert_eq!(merged, [9, 8, 7, 5, 3, 2, 1]);
+
+        // Equal keys come out in the order of their iterators.
+        let merged: Vec<(u32, char)> = k_way_merge_by_key(
+            [vec![(1, 'a'), (2, 'a'), (2, 'b')], vec![(1, 'c'), (2, 'c')], vec![(0, 'd'), (2, 'd')]],
+            |&(key, _)| key,
+        ).collect();
+        assert_eq!(merged, [(0, 'd'), (1, 'a'), (1, 'c'), (2, 'a'), (2, 'b'), (2, 'c'), (2, 'd')]);
+    }
+}
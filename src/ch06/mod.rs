@@ -0,0 +1,11 @@
+mod heapsort;
+mod heap;
+mod priority_queue;
+mod d_ary_heap;
+mod k_way_merge;
+
+pub use heapsort::*;
+pub use heap::*;
+pub use priority_queue::*;
+pub use d_ary_heap::*;
+pub use k_way_merge::*;
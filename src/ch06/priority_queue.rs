@@ -0,0 +1,328 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+// Handle to an element of a `PriorityQueue`, which stays valid until the element is extracted or deleted.
+// A handle is never reused, so a stale handle cannot refer to an element inserted later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<K, V> {
+    generation: u64,
+    entry: Option<Entry<K, V>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    pos: usize, // Index of the entry in the heap
+}
+
+// Max-priority queue implemented as a binary max-heap of slot indices (section 6.5).
+// Each slot records the heap position of its entry, so the entry of a handle can be found in O(1)
+// and `increase_key`, `decrease_key` and `delete` run in O(lg n).
+#[derive(Debug, Clone)]
+pub struct PriorityQueue<K, V> {
+    heap: Vec<usize>,
+    slots: Vec<Slot<K, V>>,
+    free: Vec<usize>,
+}
+
+impl<K: PartialOrd, V> PriorityQueue<K, V> {
+    pub fn new() -> Self {
+        PriorityQueue { heap: Vec::new(), slots: Vec::new(), free: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, h: Handle) -> bool {
+        self.entry(h).is_some()
+    }
+
+    pub fn get(&self, h: Handle) -> Option<(&K, &V)> {
+        self.entry(h).map(|e| (&e.key, &e.value))
+    }
+
+    pub fn maximum(&self) -> Option<(&K, &V)> {
+        let &slot = self.heap.first()?;
+        let e = self.slots[slot].entry.as_ref().unwrap();
+        Some((&e.key, &e.value))
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Handle {
+        let pos = self.heap.len();
+        let entry = Some(Entry { key, value, pos });
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].entry = entry;
+                slot
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, entry });
+                self.slots.len() - 1
+            }
+        };
+        self.heap.push(slot);
+        self.sift_up(pos);
+        Handle { slot, generation: self.slots[slot].generation }
+    }
+
+    pub fn extract_max(&mut self) -> Option<(K, V)> {
+        let &slot = self.heap.first()?;
+        self.remove_at(0);
+        Some(self.release(slot))
+    }
+
+    pub fn increase_key(&mut self, h: Handle, key: K) {
+        let e = self.entry_mut(h).expect("invalid handle");
+        if utils::partial_compare(&key, &e.key) == Ordering::Less {
+            panic!("new key is smaller than current key");
+        }
+        e.key = key;
+        let pos = e.pos;
+        self.sift_up(pos);
+    }
+
+    pub fn decrease_key(&mut self, h: Handle, key: K) {
+        let e = self.entry_mut(h).expect("invalid handle");
+        if utils::partial_compare(&key, &e.key) == Ordering::Greater {
+            panic!("new key is larger than current key");
+        }
+        e.key = key;
+        let pos = e.pos;
+        self.sift_down(pos);
+    }
+
+    // Remove the element of `h`, returning `None` if `h` is no longer valid.
+    pub fn delete(&mut self, h: Handle) -> Option<(K, V)> {
+        let pos = self.entry(h)?.pos;
+        self.remove_at(pos);
+        Some(self.release(h.slot))
+    }
+
+    fn entry(&self, h: Handle) -> Option<&Entry<K, V>> {
+        let slot = self.slots.get(h.slot)?;
+        if slot.generation != h.generation { return None; }
+        slot.entry.as_ref()
+    }
+
+    fn entry_mut(&mut self, h: Handle) -> Option<&mut Entry<K, V>> {
+        let slot = self.slots.get_mut(h.slot)?;
+        if slot.generation != h.generation { return None; }
+        slot.entry.as_mut()
+    }
+
+    fn key(&self, pos: usize) -> &K {
+        &self.slots[self.heap[pos]].entry.as_ref().unwrap().key
+    }
+
+    fn greater(&self, i: usize, j: usize) -> bool {
+        utils::partial_compare(self.key(i), self.key(j)) == Ordering::Greater
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.slots[self.heap[i]].entry.as_mut().unwrap().pos = i;
+        self.slots[self.heap[j]].entry.as_mut().unwrap().pos = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.greater(i, parent) { break; }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    // MAX-HEAPIFY, keeping the positions of the entries up to date.
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.heap.len();
+        loop {
+            let l = 2 * i + 1;
+            let r = 2 * i + 2;
+            let mut largest = i;
+            if l < n && self.greater(l, largest) {
+                largest = l;
+            }
+            if r < n && self.greater(r, largest) {
+                largest = r;
+            }
+            if largest == i { return; }
+            self.swap(i, largest);
+            i = largest;
+        }
+    }
+
+    // Remove the heap node at `pos` by moving the last node into its place,
+    // which may then have to float either up or down.
+    fn remove_at(&mut self, pos: usize) {
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        self.heap.pop();
+        if pos < last {
+            self.sift_up(pos);
+            self.sift_down(pos);
+        }
+    }
+
+    fn release(&mut self, slot: usize) -> (K, V) {
+        let s = &mut self.slots[slot];
+        let e = s.entry.take().unwrap();
+        s.generation += 1;
+        self.free.push(slot);
+        (e.key, e.value)
+    }
+}
+
+impl<K: PartialOrd, V> Default for PriorityQueue<K, V> {
+    fn default() -> Self {
+        PriorityQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use rand::Rng;
+    use super::*;
+
+    // Check the max-heap property and that every entry records its own heap position.
+    fn check<K: PartialOrd, V>(q: &PriorityQueue<K, V>) {
+        for i in 1..q.heap.len() {
+            assert!(!q.greater(i, (i - 1) / 2), "heap property violated at {i}");
+        }
+        for (pos, &slot) in q.heap.iter().enumerate() {
+            assert_eq!(q.slots[slot].entry.as_ref().unwrap().pos, pos);
+        }
+        let live = q.slots.iter().filter(|s| s.entry.is_some()).count();
+        assert_eq!(live, q.heap.len());
+    }
+
+    #[test]
+    fn priority_queue_test() {
+        let mut q = PriorityQueue::new();
+        assert!(q.is_empty());
+        assert_eq!(q.maximum(), None);
+        assert_eq!(q.extract_max(), None);
+
+        let a = q.insert(4, 'a');
+        let b = q.insert(1, 'b');
+        let c = q.insert(3, 'c');
+        let d = q.insert(2, 'd');
+        check(&q);
+        assert_eq!(q.len(), 4);
+        assert_eq!(q.maximum(), Some((&4, &'a')));
+
+        q.increase_key(b, 5);
+        assert_eq!(q.maximum(), Some((&5, &'b')));
+        q.decrease_key(b, 0);
+        assert_eq!(q.maximum(), Some((&4, &'a')));
+        assert_eq!(q.get(b), Some((&0, &'b')));
+        check(&q);
+
+        assert_eq!(q.delete(c), Some((3, 'c')));
+        assert_eq!(q.delete(c), None);
+        assert!(!q.contains(c));
+        check(&q);
+
+        assert_eq!(q.extract_max(), Some((4, 'a')));
+        assert!(!q.contains(a));
+        let e = q.insert(7, 'e');
+        assert_ne!(e, a);
+        assert_eq!(q.get(a), None);
+        assert_eq!(q.extract_max(), Some((7, 'e')));
+        assert_eq!(q.extract_max(), Some((2, 'd')));
+        assert!(q.contains(b) && !q.contains(d));
+        assert_eq!(q.extract_max(), Some((0, 'b')));
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "new key is smaller than current key")]
+    fn increase_key_error() {
+        let mut q = PriorityQueue::new();
+        let h = q.insert(5, ());
+        q.increase_key(h, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "new key is larger than current key")]
+    fn decrease_key_error() {
+        let mut q = PriorityQueue::new();
+        let h = q.insert(5, ());
+        q.decrease_key(h, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid handle")]
+    fn stale_handle_error() {
+        let mut q = PriorityQueue::new();
+        let h = q.insert(5, ());
+        q.extract_max();
+        q.insert(5, ());
+        q.increase_key(h, 6);
+    }
+
+    // Apply random operations, checking the heap invariant and comparing against a model after each of them.
+    #[test]
+    fn priority_queue_random_operations() {
+        let mut rng = rand::rng();
+        for _ in 0..20 {
+            let mut q = PriorityQueue::new();
+            let mut model: HashMap<Handle, i32> = HashMap::new();
+            let mut handles: Vec<Handle> = Vec::new();
+            for value in 0..300 {
+                match rng.random_range(0..6) {
+                    0 | 1 => {
+                        let key = rng.random_range(-100..100);
+                        let h = q.insert(key, value);
+                        model.insert(h, key);
+                        handles.push(h);
+                    }
+                    2 => {
+                        let expected = model.values().max().copied();
+                        let got = q.extract_max().map(|(key, _)| key);
+                        assert_eq!(got, expected);
+                        model.retain(|h, _| q.contains(*h));
+                    }
+                    3 if !handles.is_empty() => {
+                        let h = handles[rng.random_range(0..handles.len())];
+                        if let Some(key) = model.get_mut(&h) {
+                            *key += rng.random_range(0..50);
+                            q.increase_key(h, *key);
+                        }
+                    }
+                    4 if !handles.is_empty() => {
+                        let h = handles[rng.random_range(0..handles.len())];
+                        if let Some(key) = model.get_mut(&h) {
+                            *key -= rng.random_range(0..50);
+                            q.decrease_key(h, *key);
+                        }
+                    }
+                    5 if !handles.is_empty() => {
+                        let h = handles[rng.random_range(0..handles.len())];
+                        assert_eq!(q.delete(h).map(|(key, _)| key), model.remove(&h));
+                    }
+                    _ => {}
+                }
+                check(&q);
+                assert_eq!(q.len(), model.len());
+                assert_eq!(q.maximum().map(|(&key, _)| key), model.values().max().copied());
+                for (&h, &key) in &model {
+                    assert_eq!(q.get(h).map(|(&k, _)| k), Some(key));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+use std::mem;
+use rand::Rng;
+use crate::ch02;
+use crate::ch06;
+use crate::ch07::hoare_partition_by;
+use crate::utils;
+
+/*
+    Introsort
+    Randomized quicksort with Hoare partitioning, which stops partitioning subarrays of at most
+    `INSERTION_SORT_CUTOFF` elements and hands them to `insertion_sort`. Hoare partitioning stops at keys equal
+    to the pivot from both sides, so runs of equal keys are split evenly instead of degrading the partition.
+    If a subarray is still being partitioned at depth 2⌊lg n⌋, the pivots have been unlucky, so the subarray is
+    sorted by `heapsort` instead. Each level of partitioning costs O(n) in total, and the heapsorted subarrays
+    cost O(n lg n) in total, so the worst case is O(n lg n) whatever the input and the random choices.
+*/
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+pub fn introsort<T: PartialOrd>(a: &mut [T]) {
+    introsort_by(a, utils::partial_compare);
+}
+
+// The sort is unstable, and since it only swaps elements, `a` is left a permutation of itself if `compare` panics.
+pub fn introsort_by<T, F>(a: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let depth_limit = 2 * a.len().max(1).ilog2();
+    introsort_aux(a, depth_limit, &mut compare, &mut rand::rng());
+}
+
+pub fn introsort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    introsort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+// Like `quicksort_with`, recurse into the smaller side of each partition and loop on the larger one,
+// so the stack depth stays O(lg n). Both sides are charged one level of `depth_limit`.
+fn introsort_aux<T, F, R>(mut a: &mut [T], mut depth_limit: u32, compare: &mut F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    while a.len() > INSERTION_SORT_CUTOFF {
+        if depth_limit == 0 {
+            ch06::heapsort_by(a, &mut *compare);
+            return;
+        }
+        depth_limit -= 1;
+
+        let x = rng.random_range(0..a.len());
+        a.swap(x, 0);
+        let j = hoare_partition_by(a, compare);
+
+        let (left, right) = mem::take(&mut a).split_at_mut(j + 1);
+        if left.len() < right.len() {
+            introsort_aux(left, depth_limit, compare, rng);
+            a = right;
+        } else {
+            introsort_aux(right, depth_limit, compare, rng);
+            a = left;
+        }
+    }
+    ch02::insertion_sort_by(a, &mut *compare);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn introsort_test() {
+        ch02::tests::sort_i32(introsort);
+        ch02::tests::sort_f64(introsort);
+        ch02::tests::sort_char(introsort);
+        ch02::tests::sort_str(|a| introsort(a));
+        ch02::tests::sort_string(introsort);
+        ch02::tests::sort_adversarial(introsort);
+    }
+
+    #[test]
+    fn introsort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| introsort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| introsort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| introsort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| introsort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| introsort_by_key(a, key));
+        ch02::tests::sort_by_panic_permutes(|a, compare| introsort_by(a, compare));
+    }
+
+    #[test]
+    fn introsort_comparisons_test() {
+        // No input may cost more than a small multiple of n lg n comparisons.
+        let mut rng = rand::rng();
+        let n: u32 = 1 << 14;
+        let mut shuffled: Vec<u32> = (0..n).collect();
+        shuffled.shuffle(&mut rng);
+        let cases: [Vec<u32>; 6] = [
+            shuffled,
+            (0..n).collect(),
+            (0..n).rev().collect(),
+            vec![0; n as usize],
+            (0..n).map(|i| i % 2).collect(),
+            (0..n).map(|i| if i < n / 2 { i } else { n - i }).collect(),
+        ];
+        let bound = 3 * n as usize * n.ilog2() as usize;
+        for mut a in cases {
+            let mut comparisons = 0;
+            introsort_by(&mut a, |x, y| {
+                comparisons += 1;
+                x.cmp(y)
+            });
+            assert!(a.is_sorted());
+            assert!(comparisons <= bound, "{comparisons} > {bound}");
+        }
+    }
+
+    #[test]
+    fn introsort_depth_limit_test() {
+        // A depth limit of 0 falls back to heapsort at once, and small limits mix all three sorts.
+        let mut rng = rand::rng();
+        for depth_limit in [0, 1, 2, 4] {
+            let mut a: Vec<u32> = (0..1000).map(|_| rng.random_range(0..100)).collect();
+            let mut expected = a.clone();
+            expected.sort();
+            introsort_aux(&mut a, depth_limit, &mut |x: &u32, y: &u32| x.cmp(y), &mut rng);
+            assert_eq!(a, expected);
+        }
+    }
+}
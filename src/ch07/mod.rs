@@ -0,0 +1,7 @@
+mod partition;
+mod quicksort;
+mod introsort;
+
+pub use partition::*;
+pub use quicksort::*;
+pub use introsort::*;
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+// Lomuto partition (PARTITION) around the pivot `a[a.len() - 1]`.
+// Return the final index `q` of the pivot, where `a[..q] <= a[q] < a[q + 1..]`.
+pub fn partition<T: PartialOrd>(a: &mut [T]) -> usize {
+    partition_by(a, &mut utils::partial_compare)
+}
+
+pub fn partition_by<T, F>(a: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(!a.is_empty(), "cannot partition an empty slice");
+    let r = a.len() - 1;
+    let mut i = 0;
+    for j in 0..r {
+        if compare(&a[j], &a[r]) != Ordering::Greater {
+            a.swap(i, j);
+            i += 1;
+        }
+    }
+    a.swap(i, r);
+    i
+}
+
+// Hoare partition (Problem 7-1) around the pivot `a[0]`, where `a.len() >= 2`.
+// Return `j < a.len() - 1` such that every element of `a[..=j]` is at most every element of `a[j + 1..]`.
+// The pivot is not necessarily at its final position.
+pub fn hoare_partition<T: PartialOrd>(a: &mut [T]) -> usize {
+    hoare_partition_by(a, &mut utils::partial_compare)
+}
+
+pub fn hoare_partition_by<T, F>(a: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(a.len() >= 2, "cannot Hoare-partition fewer than 2 elements");
+    // The pivot `x` is compared by position, which moves whenever the pivot is exchanged.
+    let mut x = 0;
+    let mut i = 0;
+    let mut j = a.len();
+    let mut first = true;
+    loop {
+        // repeat j = j - 1 until a[j] <= x
+        j -= 1;
+        while compare(&a[j], &a[x]) == Ordering::Greater {
+            j -= 1;
+        }
+        // repeat i = i + 1 until a[i] >= x, starting from i = -1
+        if !first { i += 1; }
+        first = false;
+        while compare(&a[i], &a[x]) == Ordering::Less {
+            i += 1;
+        }
+        if i >= j { return j; }
+        a.swap(i, j);
+        if x == i {
+            x = j;
+        } else if x == j {
+            x = i;
+        }
+    }
+}
+
+// Three-way partition (Problem 7-2) around the pivot `a[0]`, which groups the elements equal to the pivot together.
+// Return `(q, t)` such that `a[..q] < a[q..t] == pivot < a[t..]`, where `q < t`.
+pub fn three_way_partition<T: PartialOrd>(a: &mut [T]) -> (usize, usize) {
+    three_way_partition_by(a, &mut utils::partial_compare)
+}
+
+pub fn three_way_partition_by<T, F>(a: &mut [T], compare: &mut F) -> (usize, usize)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(!a.is_empty(), "cannot partition an empty slice");
+    // a[..lt] < x, a[lt..i] == x, a[i..gt] is unknown, and a[gt..] > x.
+    // a[lt..i] always holds the pivot or an element equal to it, so a[lt] serves as `x`.
+    let mut lt = 0;
+    let mut i = 1;
+    let mut gt = a.len();
+    while i < gt {
+        match compare(&a[i], &a[lt]) {
+            Ordering::Less => {
+                a.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                a.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn partition_test() {
+        // Figure 7.1
+        let mut a = [2, 8, 7, 1, 3, 5, 6, 4];
+        assert_eq!(partition(&mut a), 3);
+        assert_eq!(a, [2, 1, 3, 4, 7, 5, 6, 8]);
+
+        let mut a = [1];
+        assert_eq!(partition(&mut a), 0);
+        let mut a = [3, 3, 3];
+        assert_eq!(partition(&mut a), 2);
+    }
+
+    #[test]
+    fn hoare_partition_test() {
+        // Problem 7-1 (a)
+        let mut a = [13, 19, 9, 5, 12, 8, 7, 4, 11, 2, 6, 21];
+        assert_eq!(hoare_partition(&mut a), 8);
+        assert_eq!(a, [6, 2, 9, 5, 12, 8, 7, 4, 11, 19, 13, 21]);
+
+        let mut a = [3, 3];
+        assert_eq!(hoare_partition(&mut a), 0);
+    }
+
+    #[test]
+    fn three_way_partition_test() {
+        let mut a = [4, 1, 4, 7, 4, 2, 9, 4];
+        let (q, t) = three_way_partition(&mut a);
+        assert_eq!((q, t), (2, 6));
+        assert!(a[..q].iter().all(|&x| x < 4));
+        assert!(a[q..t].iter().all(|&x| x == 4));
+        assert!(a[t..].iter().all(|&x| x > 4));
+
+        let mut a = [5, 5, 5];
+        assert_eq!(three_way_partition(&mut a), (0, 3));
+    }
+
+    #[test]
+    fn partition_random_test() {
+        let mut rng = rand::rng();
+        for n in 1..50 {
+            let a: Vec<i32> = (0..n).map(|_| rng.random_range(0..10)).collect();
+
+            let mut b = a.clone();
+            let pivot = b[n - 1];
+            let q = partition(&mut b);
+            assert_eq!(b[q], pivot);
+            assert!(b[..q].iter().all(|&x| x <= pivot) && b[q + 1..].iter().all(|&x| x > pivot));
+
+            if n >= 2 {
+                let mut b = a.clone();
+                let j = hoare_partition(&mut b);
+                assert!(j < n - 1);
+                let max_left = b[..=j].iter().max().unwrap();
+                let min_right = b[j + 1..].iter().min().unwrap();
+                assert!(max_left <= min_right, "{b:?}, {j}");
+            }
+
+            let mut b = a.clone();
+            let pivot = b[0];
+            let (q, t) = three_way_partition(&mut b);
+            assert!(q < t);
+            assert!(b[..q].iter().all(|&x| x < pivot));
+            assert!(b[q..t].iter().all(|&x| x == pivot));
+            assert!(b[t..].iter().all(|&x| x > pivot));
+        }
+    }
+}
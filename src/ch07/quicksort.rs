@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+use std::mem;
+use rand::Rng;
+use crate::ch07::{hoare_partition_by, partition_by, three_way_partition_by};
+use crate::utils;
+
+// How `quicksort_with` partitions each subarray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    // `partition`, which puts the pivot at its final position.
+    Lomuto,
+    // `hoare_partition` (Problem 7-1), which does fewer swaps than Lomuto.
+    Hoare,
+    // `three_way_partition` (Problem 7-2), which takes all keys equal to the pivot out of the recursion.
+    ThreeWay,
+}
+
+// How `quicksort_with` chooses the pivot of each subarray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivot {
+    // The element the partition scheme uses as is: the last one for Lomuto, and the first one otherwise.
+    Fixed,
+    // An element chosen uniformly at random (RANDOMIZED-PARTITION).
+    Random,
+    // The median of 3 elements chosen at random (Problem 7-5).
+    MedianOf3,
+}
+
+// The original QUICKSORT, which partitions around the last element.
+// It takes Θ(n^2) time on sorted or all-equal input, but its stack depth is O(lg n) like every variant here.
+// The sort is unstable, and since it only swaps elements, `a` is left a permutation of itself if `compare` panics.
+pub fn quicksort<T: PartialOrd>(a: &mut [T]) {
+    quicksort_by(a, utils::partial_compare);
+}
+
+pub fn quicksort_by<T, F>(a: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quicksort_with_by(a, PartitionScheme::Lomuto, Pivot::Fixed, compare);
+}
+
+pub fn quicksort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    quicksort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+// RANDOMIZED-QUICKSORT, which runs in O(n lg n) expected time on any input of distinct keys.
+pub fn randomized_quicksort<T: PartialOrd>(a: &mut [T]) {
+    randomized_quicksort_by(a, utils::partial_compare);
+}
+
+pub fn randomized_quicksort_by<T, F>(a: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quicksort_with_by(a, PartitionScheme::Lomuto, Pivot::Random, compare);
+}
+
+pub fn randomized_quicksort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: PartialOrd,
+    F: FnMut(&T) -> K,
+{
+    randomized_quicksort_by(a, |x, y| utils::partial_compare(&key(x), &key(y)));
+}
+
+// Quicksort with any combination of partition scheme and pivot rule.
+pub fn quicksort_with<T: PartialOrd>(a: &mut [T], scheme: PartitionScheme, pivot: Pivot) {
+    quicksort_with_by(a, scheme, pivot, utils::partial_compare);
+}
+
+pub fn quicksort_with_by<T, F>(a: &mut [T], scheme: PartitionScheme, pivot: Pivot, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    quicksort_aux(a, scheme, pivot, &mut compare, &mut rand::rng());
+}
+
+// Tail-recursion elimination (Problem 7-4): recurse into the smaller side of each partition and loop on the larger one.
+// Every recursive call gets at most half of the elements, so the stack depth is O(lg n) even when the running time is Θ(n^2).
+fn quicksort_aux<T, F, R>(mut a: &mut [T], scheme: PartitionScheme, pivot: Pivot, compare: &mut F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    while a.len() > 1 {
+        let n = a.len();
+        let x = match pivot {
+            Pivot::Fixed => None,
+            Pivot::Random => Some(rng.random_range(0..n)),
+            Pivot::MedianOf3 if n < 3 => Some(rng.random_range(0..n)),
+            Pivot::MedianOf3 => {
+                let (i, j, k) = (rng.random_range(0..n), rng.random_range(0..n), rng.random_range(0..n));
+                Some(median_of_3(a, i, j, k, compare))
+            }
+        };
+
+        // The subarrays left to sort are `a[..left_end]` and `a[right_start..]`.
+        let (left_end, right_start) = match scheme {
+            PartitionScheme::Lomuto => {
+                if let Some(x) = x { a.swap(x, n - 1); }
+                let q = partition_by(a, compare);
+                (q, q + 1)
+            }
+            PartitionScheme::Hoare => {
+                if let Some(x) = x { a.swap(x, 0); }
+                let j = hoare_partition_by(a, compare);
+                (j + 1, j + 1)
+            }
+            PartitionScheme::ThreeWay => {
+                if let Some(x) = x { a.swap(x, 0); }
+                three_way_partition_by(a, compare)
+            }
+        };
+
+        let (left, right) = mem::take(&mut a).split_at_mut(left_end);
+        let right = &mut right[right_start - left_end..];
+        if left.len() < right.len() {
+            quicksort_aux(left, scheme, pivot, compare, rng);
+            a = right;
+        } else {
+            quicksort_aux(right, scheme, pivot, compare, rng);
+            a = left;
+        }
+    }
+}
+
+// Return whichever of the indices `i`, `j` and `k` holds the median of their elements.
+fn median_of_3<T, F>(a: &[T], i: usize, j: usize, k: usize, compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut less = |x: usize, y: usize| compare(&a[x], &a[y]) == Ordering::Less;
+    if less(i, j) {
+        if less(j, k) { j } else if less(i, k) { k } else { i }
+    } else if less(i, k) {
+        i
+    } else if less(j, k) {
+        k
+    } else {
+        j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use rand::seq::SliceRandom;
+    use crate::ch02;
+    use super::*;
+
+    const SCHEMES: [PartitionScheme; 3] = [PartitionScheme::Lomuto, PartitionScheme::Hoare, PartitionScheme::ThreeWay];
+    const PIVOTS: [Pivot; 3] = [Pivot::Fixed, Pivot::Random, Pivot::MedianOf3];
+
+    #[test]
+    fn quicksort_test() {
+        ch02::tests::sort_i32(quicksort);
+        ch02::tests::sort_f64(quicksort);
+        ch02::tests::sort_char(quicksort);
+        ch02::tests::sort_str(|a| quicksort(a));
+        ch02::tests::sort_string(quicksort);
+        ch02::tests::sort_adversarial(quicksort);
+    }
+
+    #[test]
+    fn quicksort_by_test() {
+        ch02::tests::sort_by_i32(|a, compare| quicksort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| quicksort_by_key(a, key));
+        ch02::tests::sort_by_key_pair(|a, key| quicksort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| quicksort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| quicksort_by_key(a, key));
+    }
+
+    #[test]
+    fn randomized_quicksort_test() {
+        ch02::tests::sort_i32(randomized_quicksort);
+        ch02::tests::sort_f64(randomized_quicksort);
+        ch02::tests::sort_char(randomized_quicksort);
+        ch02::tests::sort_str(|a| randomized_quicksort(a));
+        ch02::tests::sort_string(randomized_quicksort);
+        ch02::tests::sort_adversarial(randomized_quicksort);
+        ch02::tests::sort_by_i32(|a, compare| randomized_quicksort_by(a, compare));
+        ch02::tests::sort_by_key_i32(|a, key| randomized_quicksort_by_key(a, key));
+        ch02::tests::sort_by_struct(|a, compare| randomized_quicksort_by(a, compare));
+        ch02::tests::sort_by_key_struct(|a, key| randomized_quicksort_by_key(a, key));
+    }
+
+    #[test]
+    fn quicksort_with_test() {
+        // Non-capturing closures cannot read `scheme` and `pivot`, so each combination is spelled out.
+        let sorts: [fn(&mut [i32]); 9] = [
+            |a| quicksort_with(a, PartitionScheme::Lomuto, Pivot::Fixed),
+            |a| quicksort_with(a, PartitionScheme::Lomuto, Pivot::Random),
+            |a| quicksort_with(a, PartitionScheme::Lomuto, Pivot::MedianOf3),
+            |a| quicksort_with(a, PartitionScheme::Hoare, Pivot::Fixed),
+            |a| quicksort_with(a, PartitionScheme::Hoare, Pivot::Random),
+            |a| quicksort_with(a, PartitionScheme::Hoare, Pivot::MedianOf3),
+            |a| quicksort_with(a, PartitionScheme::ThreeWay, Pivot::Fixed),
+            |a| quicksort_with(a, PartitionScheme::ThreeWay, Pivot::Random),
+            |a| quicksort_with(a, PartitionScheme::ThreeWay, Pivot::MedianOf3),
+        ];
+        for sort in sorts {
+            ch02::tests::sort_i32(sort);
+            ch02::tests::sort_adversarial(sort);
+        }
+
+        let sorts_by: [ch02::tests::SortBy<String>; 3] = [
+            |a, compare| quicksort_with_by(a, PartitionScheme::Hoare, Pivot::MedianOf3, compare),
+            |a, compare| quicksort_with_by(a, PartitionScheme::ThreeWay, Pivot::Random, compare),
+            |a, compare| quicksort_with_by(a, PartitionScheme::Lomuto, Pivot::MedianOf3, compare),
+        ];
+        for sort_by in sorts_by {
+            ch02::tests::sort_by_panic_permutes(sort_by);
+        }
+
+        let mut rng = rand::rng();
+        for scheme in SCHEMES {
+            for pivot in PIVOTS {
+                for n in 0..40 {
+                    let mut a: Vec<u8> = (0..n).map(|_| rng.random_range(0..5)).collect();
+                    let mut expected = a.clone();
+                    expected.sort();
+                    quicksort_with(&mut a, scheme, pivot);
+                    assert_eq!(a, expected, "{scheme:?}, {pivot:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quicksort_by_panic_test() {
+        ch02::tests::sort_by_panic_permutes(|a, compare| quicksort_by(a, compare));
+        ch02::tests::sort_by_panic_permutes(|a, compare| randomized_quicksort_by(a, compare));
+    }
+
+    #[test]
+    fn quicksort_stack_depth_test() {
+        // Sorted input makes every fixed-pivot partition maximally unbalanced, which would recurse n deep
+        // without tail-recursion elimination and overflow this small stack.
+        thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                for scheme in SCHEMES {
+                    let mut a: Vec<u32> = (0..5000).collect();
+                    quicksort_with(&mut a, scheme, Pivot::Fixed);
+                    assert!(a.is_sorted());
+                }
+                let mut a: Vec<u32> = (0..5000).rev().collect();
+                quicksort(&mut a);
+                assert!(a.is_sorted());
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn three_way_quicksort_duplicates_test() {
+        // With only a few distinct keys, three-way partitioning runs in linear time per distinct key.
+        let mut rng = rand::rng();
+        let mut a: Vec<u32> = (0..100_000).map(|i| i % 4).collect();
+        a.shuffle(&mut rng);
+        let mut comparisons = 0;
+        quicksort_with_by(&mut a, PartitionScheme::ThreeWay, Pivot::Random, |x, y| {
+            comparisons += 1;
+            x.cmp(y)
+        });
+        assert!(a.is_sorted());
+        assert!(comparisons <= 4 * a.len(), "{comparisons}");
+    }
+
+    #[test]
+    fn median_of_3_test() {
+        let a = [1, 2, 3];
+        let mut compare = |x: &i32, y: &i32| x.cmp(y);
+        for (i, j, k) in [(0, 1, 2), (0, 2, 1), (1, 0, 2), (1, 2, 0), (2, 0, 1), (2, 1, 0)] {
+            assert_eq!(median_of_3(&a, i, j, k, &mut compare), 1);
+        }
+        let b = [5, 5, 1];
+        assert_eq!(b[median_of_3(&b, 0, 1, 2, &mut compare)], 5);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::ch02;
+use crate::ch08::counting_sort_by_key;
+use crate::utils;
+
+/*
+    Bucket Sort
+    BUCKET-SORT divides [0, 1) into n buckets of equal size, distributes the n keys into the buckets,
+    sorts each bucket by insertion sort and concatenates the buckets.
+    It runs in Θ(n) expected time if the keys are drawn uniformly from [0, 1).
+    Instead of linked lists, the elements are distributed by a stable counting sort on their bucket numbers,
+    which leaves each bucket as a contiguous subarray of `a`.
+*/
+pub fn bucket_sort(a: &mut [f64]) {
+    bucket_sort_by_key(a, |&x| x);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn bucket_sort_by_key<T, F>(a: &mut [T], mut key: F)
+where
+    F: FnMut(&T) -> f64,
+{
+    let n = a.len();
+    if n == 0 { return; }
+    counting_sort_by_key(a, n - 1, |x| bucket(key(x), n));
+
+    let mut start = 0;
+    while start < n {
+        let b = bucket(key(&a[start]), n);
+        let mut end = start + 1;
+        while end < n && bucket(key(&a[end]), n) == b {
+            end += 1;
+        }
+        ch02::insertion_sort_by(&mut a[start..end], |x, y| utils::partial_compare(&key(x), &key(y)));
+        start = end;
+    }
+}
+
+// Return the bucket `⌊n·key⌋` of `key` among `n` buckets.
+fn bucket(key: f64, n: usize) -> usize {
+    assert!((0.0..1.0).contains(&key), "key {key} is out of range [0, 1)");
+    // Rounding may take `n·key` up to `n` when `key` is just below 1.
+    ((key * n as f64) as usize).min(n - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn bucket_sort_test() {
+        // Figure 8.4
+        let mut a = [0.78, 0.17, 0.39, 0.26, 0.72, 0.94, 0.21, 0.12, 0.23, 0.68];
+        bucket_sort(&mut a);
+        assert_eq!(a, [0.12, 0.17, 0.21, 0.23, 0.26, 0.39, 0.68, 0.72, 0.78, 0.94]);
+
+        let mut a = [];
+        bucket_sort(&mut a);
+        let mut a = [1.0 - f64::EPSILON / 2.0, 0.0, 0.5];
+        bucket_sort(&mut a);
+        assert_eq!(a, [0.0, 0.5, 1.0 - f64::EPSILON / 2.0]);
+
+        let mut rng = rand::rng();
+        let mut a: Vec<f64> = (0..1000).map(|_| rng.random()).collect();
+        let mut expected = a.clone();
+        expected.sort_by(f64::total_cmp);
+        bucket_sort(&mut a);
+        assert_eq!(a, expected);
+    }
+
+    #[test]
+    fn bucket_sort_by_key_test() {
+        ch02::tests::sort_char(|a| bucket_sort_by_key(a, |&c| c as u32 as f64 / 128.0));
+        ch02::tests::sort_by_key_struct(|a, key| bucket_sort_by_key(a, |x| key(x) as f64 / 100.0));
+        ch02::tests::sort_by_key_stable(|a, key| bucket_sort_by_key(a, |x| key(x) as f64 / 16.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "key 1 is out of range [0, 1)")]
+    fn bucket_sort_out_of_range_test() {
+        bucket_sort(&mut [0.5, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "key NaN is out of range [0, 1)")]
+    fn bucket_sort_nan_test() {
+        bucket_sort(&mut [0.5, f64::NAN]);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::utils::ops::Unsigned;
+
+/*
+    Counting Sort
+    COUNTING-SORT places each element directly at its final position, computed from the number of keys
+    less than or equal to its own, so it sorts n keys in `0..=k` in Θ(n + k) time without comparing them.
+    Instead of writing into an output array B, the final positions are applied to `a` in place
+    by following the cycles of the permutation, so elements are only swapped, never cloned.
+*/
+pub fn counting_sort<T: Unsigned>(a: &mut [T], k: T) {
+    counting_sort_by_key(a, k.to_u64() as usize, |x| x.to_u64() as usize);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn counting_sort_by_key<T, F>(a: &mut [T], k: usize, mut key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = a.iter().map(|x| {
+        let key = key(x);
+        assert!(key <= k, "key {key} is out of range 0..={k}");
+        key
+    }).collect();
+
+    let mut c = vec![0; k + 1];
+    for &key in &keys {
+        c[key] += 1;
+    }
+    // `c[i]` now contains the number of elements equal to `i`. Make it the number less than or equal to `i`.
+    for i in 1..=k {
+        c[i] += c[i - 1];
+    }
+
+    // Scan from the end so that the last of the equal keys takes the last of their positions.
+    let mut dest = vec![0; a.len()];
+    for j in (0..a.len()).rev() {
+        c[keys[j]] -= 1;
+        dest[j] = c[keys[j]];
+    }
+    permute(a, &mut dest);
+}
+
+// Move each `a[i]` to `a[dest[i]]` by swapping along the cycles of `dest`, which is left the identity.
+fn permute<T>(a: &mut [T], dest: &mut [usize]) {
+    for i in 0..a.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            a.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn counting_sort_test() {
+        // Figure 8.2
+        let mut a: [u8; 8] = [2, 5, 3, 0, 2, 3, 0, 3];
+        counting_sort(&mut a, 5);
+        assert_eq!(a, [0, 0, 2, 2, 3, 3, 3, 5]);
+
+        let mut a: [u32; 0] = [];
+        counting_sort(&mut a, 0);
+        let mut a = [0usize; 5];
+        counting_sort(&mut a, 0);
+        assert_eq!(a, [0; 5]);
+
+        let mut rng = rand::rng();
+        let mut a: Vec<u16> = (0..1000).map(|_| rng.random_range(0..100)).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        counting_sort(&mut a, 99);
+        assert_eq!(a, expected);
+    }
+
+    #[test]
+    fn counting_sort_by_key_test() {
+        ch02::tests::sort_char(|a| counting_sort_by_key(a, 127, |&c| c as usize));
+        ch02::tests::sort_by_key_struct(|a, key| counting_sort_by_key(a, 100, |x| key(x) as usize));
+        ch02::tests::sort_by_key_stable(|a, key| counting_sort_by_key(a, 15, |x| key(x) as usize));
+
+        let mut a = [(3, 'a'), (1, 'b'), (3, 'c'), (0, 'd'), (1, 'e')];
+        counting_sort_by_key(&mut a, 3, |&(key, _)| key);
+        assert_eq!(a, [(0, 'd'), (1, 'b'), (1, 'e'), (3, 'a'), (3, 'c')]);
+    }
+
+    #[test]
+    #[should_panic(expected = "key 6 is out of range 0..=5")]
+    fn counting_sort_out_of_range_test() {
+        counting_sort(&mut [2u8, 6, 3], 5);
+    }
+
+    #[test]
+    fn permute_test() {
+        let mut a = ['a', 'b', 'c', 'd', 'e'];
+        let mut dest = [2, 0, 1, 4, 3];
+        permute(&mut a, &mut dest);
+        assert_eq!(a, ['b', 'c', 'a', 'e', 'd']);
+        assert_eq!(dest, [0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,7 @@
+mod counting_sort;
+mod radix_sort;
+mod bucket_sort;
+
+pub use counting_sort::*;
+pub use radix_sort::*;
+pub use bucket_sort::*;
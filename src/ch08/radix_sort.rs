@@ -0,0 +1,116 @@
+use crate::ch08::counting_sort_by_key;
+use crate::utils::ops::Unsigned;
+
+/*
+    Radix Sort
+    RADIX-SORT sorts on the least significant digit first, using a stable sort for each digit,
+    so that after pass i the elements are sorted by their i lowest digits.
+    With counting sort as the stable sort, n keys of d digits in `0..radix` are sorted in Θ(d(n + radix)) time.
+*/
+// Sort `a` by `d` digits in `0..radix`, where `digit(x, i)` is the `i`-th digit of `x` counting from the least
+// significant digit 0. The sort is stable: equal elements keep their relative order.
+pub fn radix_sort_by_digits<T, F>(a: &mut [T], d: usize, radix: usize, mut digit: F)
+where
+    F: FnMut(&T, usize) -> usize,
+{
+    assert!(radix > 0, "radix must be positive");
+    for i in 0..d {
+        counting_sort_by_key(a, radix - 1, |x| digit(x, i));
+    }
+}
+
+// Sort unsigned integers one byte at a time, i.e. with radix 256 and `T::BITS / 8` digits.
+pub fn radix_sort<T: Unsigned>(a: &mut [T]) {
+    radix_sort_by_key(a, |&x| x);
+}
+
+pub fn radix_sort_by_key<T, K, F>(a: &mut [T], mut key: F)
+where
+    K: Unsigned,
+    F: FnMut(&T) -> K,
+{
+    let d = K::BITS.div_ceil(8) as usize;
+    radix_sort_by_digits(a, d, 256, |x, i| (key(x).to_u64() >> (8 * i) & 0xff) as usize);
+}
+
+// Sort strings of `width` bytes each, whose bytes are the digits with the last byte least significant.
+// Byte order is the same as the order of `str`, so UTF-8 strings are sorted as `str::cmp` would.
+pub fn radix_sort_fixed_width<S: AsRef<[u8]>>(a: &mut [S], width: usize) {
+    for s in a.iter() {
+        let len = s.as_ref().len();
+        assert_eq!(len, width, "string of length {len} does not have width {width}");
+    }
+    radix_sort_by_digits(a, width, 256, |s, i| s.as_ref()[width - 1 - i] as usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn radix_sort_by_digits_test() {
+        // Figure 8.3
+        let mut a = [329, 457, 657, 839, 436, 720, 355];
+        radix_sort_by_digits(&mut a, 3, 10, |&x, i| x / 10usize.pow(i as u32) % 10);
+        assert_eq!(a, [329, 355, 436, 457, 657, 720, 839]);
+
+        // Exercise 8.3-1
+        let mut a = ["COW", "DOG", "SEA", "RUG", "ROW", "MOB", "BOX", "TAB", "BAR", "EAR", "TAR", "DIG", "BIG", "TEA", "NOW", "FOX"];
+        radix_sort_by_digits(&mut a, 3, 26, |s, i| (s.as_bytes()[2 - i] - b'A') as usize);
+        assert_eq!(a, ["BAR", "BIG", "BOX", "COW", "DIG", "DOG", "EAR", "FOX", "MOB", "NOW", "ROW", "RUG", "SEA", "TAB", "TAR", "TEA"]);
+    }
+
+    #[test]
+    fn radix_sort_test() {
+        let mut rng = rand::rng();
+        let mut a: Vec<u64> = (0..1000).map(|_| rng.random()).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        radix_sort(&mut a);
+        assert_eq!(a, expected);
+
+        let mut a: Vec<u8> = (0..1000).map(|_| rng.random()).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        radix_sort(&mut a);
+        assert_eq!(a, expected);
+
+        let mut a: [usize; 6] = [usize::MAX, 0, 1 << 40, 255, 256, 1];
+        radix_sort(&mut a);
+        assert_eq!(a, [0, 1, 255, 256, 1 << 40, usize::MAX]);
+    }
+
+    #[test]
+    fn radix_sort_by_key_test() {
+        ch02::tests::sort_char(|a| radix_sort_by_key(a, |&c| c as u32));
+        ch02::tests::sort_by_key_struct(|a, key| radix_sort_by_key(a, key));
+        ch02::tests::sort_by_key_stable(|a, key| radix_sort_by_key(a, key));
+        ch02::tests::sort_by_key_stable(|a, key| radix_sort_by_digits(a, 2, 4, |x, i| (key(x) >> (2 * i) & 3) as usize));
+    }
+
+    #[test]
+    fn radix_sort_fixed_width_test() {
+        let mut a = ["2024-03-01", "1999-12-31", "2024-02-29", "2000-01-01"];
+        radix_sort_fixed_width(&mut a, 10);
+        assert_eq!(a, ["1999-12-31", "2000-01-01", "2024-02-29", "2024-03-01"]);
+
+        let mut rng = rand::rng();
+        let mut a: Vec<String> = (0..500).map(|_| (0..4).map(|_| rng.random_range('a'..='z')).collect()).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        radix_sort_fixed_width(&mut a, 4);
+        assert_eq!(a, expected);
+
+        let mut a = ["가나", "가가", "나가"];
+        radix_sort_fixed_width(&mut a, 6);
+        assert_eq!(a, ["가가", "가나", "나가"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "string of length 2 does not have width 3")]
+    fn radix_sort_fixed_width_panic_test() {
+        radix_sort_fixed_width(&mut ["abc", "ab", "abd"], 3);
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+use crate::utils;
+
+/*
+    Simultaneous Minimum and Maximum
+    Compare the elements in pairs: the smaller of each pair against the current minimum, and the larger
+    against the current maximum. That is 3 comparisons for every 2 elements, so at most 3⌊n/2⌋ in total.
+*/
+// Return `(minimum, maximum)` of `a`, or `None` if `a` is empty.
+pub fn minimum_maximum<T: PartialOrd>(a: &[T]) -> Option<(&T, &T)> {
+    minimum_maximum_by(a, utils::partial_compare)
+}
+
+pub fn minimum_maximum_by<T, F>(a: &[T], mut compare: F) -> Option<(&T, &T)>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = a.len();
+    // If n is odd, start with the first element alone. If n is even, start with the first pair.
+    let (mut min, mut max, start) = match n {
+        0 => return None,
+        _ if n % 2 == 1 => (&a[0], &a[0], 1),
+        _ if compare(&a[0], &a[1]) == Ordering::Greater => (&a[1], &a[0], 2),
+        _ => (&a[0], &a[1], 2),
+    };
+
+    for pair in a[start..].chunks_exact(2) {
+        let (small, large) = if compare(&pair[0], &pair[1]) == Ordering::Greater {
+            (&pair[1], &pair[0])
+        } else {
+            (&pair[0], &pair[1])
+        };
+        if compare(small, min) == Ordering::Less {
+            min = small;
+        }
+        if compare(large, max) == Ordering::Greater {
+            max = large;
+        }
+    }
+    Some((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn minimum_maximum_test() {
+        assert_eq!(minimum_maximum::<i32>(&[]), None);
+        assert_eq!(minimum_maximum(&[3]), Some((&3, &3)));
+        assert_eq!(minimum_maximum(&[3, 1]), Some((&1, &3)));
+        assert_eq!(minimum_maximum(&[5, 2, 4, 6, 1, 3]), Some((&1, &6)));
+        assert_eq!(minimum_maximum(&[-0.5, 2.5, 0.0]), Some((&-0.5, &2.5)));
+        assert_eq!(minimum_maximum(&["b", "c", "a"]), Some((&"a", &"c")));
+        assert_eq!(minimum_maximum_by(&[5, 2, 4, 6, 1, 3], |x, y| y.cmp(x)), Some((&6, &1)));
+    }
+
+    #[test]
+    fn minimum_maximum_comparisons_test() {
+        let mut rng = rand::rng();
+        for n in 0..100 {
+            let a: Vec<i32> = (0..n).map(|_| rng.random_range(0..50)).collect();
+            let mut comparisons = 0;
+            let result = minimum_maximum_by(&a, |x, y| {
+                comparisons += 1;
+                x.cmp(y)
+            });
+            assert_eq!(result, a.iter().min().zip(a.iter().max()));
+            assert!(comparisons <= 3 * (n / 2), "{comparisons} comparisons for n = {n}");
+        }
+    }
+}
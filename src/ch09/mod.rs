@@ -0,0 +1,9 @@
+mod minimum_maximum;
+mod select;
+mod quantiles;
+mod weighted_median;
+
+pub use minimum_maximum::*;
+pub use select::*;
+pub use quantiles::*;
+pub use weighted_median::*;
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+use rand::Rng;
+use crate::ch09::randomized_select_by;
+use crate::ch09::select::randomized_select_aux;
+use crate::utils;
+
+// Return the `n`-th smallest element (0-based) of `a` by `randomized_select`, which leaves `a` partitioned around it.
+pub fn nth_smallest<T: PartialOrd>(a: &mut [T], n: usize) -> &T {
+    nth_smallest_by(a, n, utils::partial_compare)
+}
+
+pub fn nth_smallest_by<T, F>(a: &mut [T], n: usize, compare: F) -> &T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    randomized_select_by(a, n, compare)
+}
+
+// Return the quantile of `a` for each fraction in `ps`, such as `&[0.5, 0.9, 0.99]` for the median, 90th and 99th percentiles.
+// The quantile for `p` is the element of (0-based) rank `⌈p·n⌉ - 1`, or the minimum for `p == 0`, so the quantile for 0.5
+// is the lower median. The distinct ranks are selected by recursing on both sides of the middle one,
+// which takes O(n lg q) expected time for q quantiles, and leaves `a` partitioned around each of them.
+pub fn quantiles<'a, T: PartialOrd>(a: &'a mut [T], ps: &[f64]) -> Vec<&'a T> {
+    quantiles_by(a, ps, utils::partial_compare)
+}
+
+pub fn quantiles_by<'a, T, F>(a: &'a mut [T], ps: &[f64], mut compare: F) -> Vec<&'a T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let n = a.len();
+    assert!(n > 0 || ps.is_empty(), "cannot take quantiles of an empty slice");
+    let ranks: Vec<usize> = ps.iter().map(|&p| {
+        assert!((0.0..=1.0).contains(&p), "quantile {p} is out of range [0, 1]");
+        ((p * n as f64).ceil() as usize).clamp(1, n) - 1
+    }).collect();
+
+    let mut distinct = ranks.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    multi_select(a, &distinct, 0, &mut compare, &mut rand::rng());
+
+    let a = &*a;
+    ranks.iter().map(|&k| &a[k]).collect()
+}
+
+// Select every rank in `ranks`, which are sorted, distinct and relative to `a` plus `offset`.
+fn multi_select<T, F, R>(a: &mut [T], ranks: &[usize], offset: usize, compare: &mut F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if ranks.is_empty() { return; }
+    let m = ranks.len() / 2;
+    let k = ranks[m] - offset;
+    randomized_select_aux(a, k, compare, rng);
+    let (left, right) = a.split_at_mut(k);
+    multi_select(left, &ranks[..m], offset, compare, rng);
+    multi_select(&mut right[1..], &ranks[m + 1..], offset + k + 1, compare, rng);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn nth_smallest_test() {
+        let mut a = [5, 2, 4, 6, 1, 3];
+        assert_eq!(*nth_smallest(&mut a, 0), 1);
+        assert_eq!(*nth_smallest(&mut a, 5), 6);
+        assert_eq!(*nth_smallest(&mut a, 2), 3);
+        assert_eq!(*nth_smallest_by(&mut a, 0, |x, y| y.cmp(x)), 6);
+    }
+
+    #[test]
+    fn quantiles_test() {
+        let mut a: Vec<u32> = (1..=100).rev().collect();
+        assert_eq!(quantiles(&mut a, &[0.5, 0.9, 0.99]), [&50, &90, &99]);
+        assert_eq!(quantiles(&mut a, &[1.0, 0.0, 0.5, 0.5, 0.001]), [&100, &1, &50, &50, &1]);
+        assert_eq!(quantiles_by(&mut a, &[0.25], |x, y| y.cmp(x)), [&76]);
+        assert!(quantiles::<u32>(&mut [], &[]).is_empty());
+
+        let mut rng = rand::rng();
+        for n in 1..100 {
+            let mut a: Vec<u32> = (0..n).map(|_| rng.random_range(0..20)).collect();
+            let ps: Vec<f64> = (0..5).map(|_| rng.random()).collect();
+            let mut sorted = a.clone();
+            sorted.sort();
+            let expected: Vec<u32> = ps.iter().map(|&p| sorted[((p * n as f64).ceil() as usize).max(1) - 1]).collect();
+            let actual: Vec<u32> = quantiles(&mut a, &ps).into_iter().copied().collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile 1.5 is out of range [0, 1]")]
+    fn quantiles_out_of_range_test() {
+        quantiles(&mut [1, 2, 3], &[0.5, 1.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot take quantiles of an empty slice")]
+    fn quantiles_empty_test() {
+        quantiles::<i32>(&mut [], &[0.5]);
+    }
+}
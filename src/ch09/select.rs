@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+use std::mem;
+use rand::Rng;
+use crate::ch02;
+use crate::ch07::three_way_partition_by;
+use crate::utils;
+
+/*
+    Selection
+    Both selection algorithms rearrange `a` in place so that `a[i]` is the element that would be at index `i`
+    if `a` were sorted, every element of `a[..i]` is at most `a[i]`, and every element of `a[i + 1..]` is at least `a[i]`.
+    The order statistic `i` is 0-based, so the i-th order statistic of the book is `i - 1` here.
+
+    Both partition with `three_way_partition`, which takes all the elements equal to the pivot out of the recursion,
+    so many duplicates only make selection faster.
+*/
+// RANDOMIZED-SELECT, which runs in Θ(n) expected time.
+pub fn randomized_select<T: PartialOrd>(a: &mut [T], i: usize) -> &T {
+    randomized_select_by(a, i, utils::partial_compare)
+}
+
+pub fn randomized_select_by<T, F>(a: &mut [T], i: usize, mut compare: F) -> &T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(i < a.len(), "order statistic {i} is out of range for length {}", a.len());
+    randomized_select_aux(a, i, &mut compare, &mut rand::rng());
+    &a[i]
+}
+
+// The recursion on one side of the partition is a tail call, so it is a loop.
+pub(super) fn randomized_select_aux<T, F, R>(mut a: &mut [T], mut i: usize, compare: &mut F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    loop {
+        let x = rng.random_range(0..a.len());
+        a.swap(x, 0);
+        let (q, t) = three_way_partition_by(a, compare);
+        if i < q {
+            a = &mut mem::take(&mut a)[..q];
+        } else if i >= t {
+            a = &mut mem::take(&mut a)[t..];
+            i -= t;
+        } else {
+            return;
+        }
+    }
+}
+
+// SELECT, which runs in Θ(n) worst-case time by partitioning around the median of the medians of groups of 5 elements.
+pub fn select<T: PartialOrd>(a: &mut [T], i: usize) -> &T {
+    select_by(a, i, utils::partial_compare)
+}
+
+pub fn select_by<T, F>(a: &mut [T], i: usize, mut compare: F) -> &T
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(i < a.len(), "order statistic {i} is out of range for length {}", a.len());
+    select_aux(a, i, &mut compare);
+    &a[i]
+}
+
+fn select_aux<T, F>(mut a: &mut [T], mut i: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    loop {
+        if a.len() < 5 {
+            ch02::insertion_sort_by(a, &mut *compare);
+            return;
+        }
+
+        // Make the length divisible by 5 by moving minima to the front.
+        while !a.len().is_multiple_of(5) {
+            let mut min = 0;
+            for j in 1..a.len() {
+                if compare(&a[j], &a[min]) == Ordering::Less {
+                    min = j;
+                }
+            }
+            a.swap(0, min);
+            if i == 0 { return; }
+            a = &mut mem::take(&mut a)[1..];
+            i -= 1;
+        }
+
+        // Sort each group `a[j], a[j + g], ..., a[j + 4g]` of 5 elements in place, which gathers the group medians into `a[2g..3g]`.
+        let g = a.len() / 5;
+        for j in 0..g {
+            for k in 1..5 {
+                let mut m = j + k * g;
+                while m > j && compare(&a[m - g], &a[m]) == Ordering::Greater {
+                    a.swap(m - g, m);
+                    m -= g;
+                }
+            }
+        }
+
+        // Find the (lower) median `x` of the group medians recursively, and partition around it.
+        // The groups of at least ⌈g/2⌉ medians are at most `x`, and each has 3 elements at most `x`,
+        // so at least 3⌈g/2⌉ elements are at most `x`. Likewise for at least `x`, so each side has at most 7n/10 elements.
+        let medians = &mut a[2 * g..3 * g];
+        select_aux(medians, (g - 1) / 2, compare);
+        a.swap(0, 2 * g + (g - 1) / 2);
+        let (q, t) = three_way_partition_by(a, compare);
+        if i < q {
+            a = &mut mem::take(&mut a)[..q];
+        } else if i >= t {
+            a = &mut mem::take(&mut a)[t..];
+            i -= t;
+        } else {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+    use crate::ch02;
+    use super::*;
+
+    type Select = fn(&mut [i32], usize);
+
+    const SELECTS: [Select; 2] = [
+        |a, i| { randomized_select(a, i); },
+        |a, i| { select(a, i); },
+    ];
+
+    fn check_selected(a: &[i32], i: usize, expected: &[i32]) {
+        assert_eq!(a[i], expected[i]);
+        assert!(a[..i].iter().all(|x| *x <= a[i]));
+        assert!(a[i + 1..].iter().all(|x| *x >= a[i]));
+        let mut sorted = a.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn select_test() {
+        let mut rng = rand::rng();
+        for select_fn in SELECTS {
+            for n in 1..60 {
+                for values in [3, 1000] {
+                    let a: Vec<i32> = (0..n).map(|_| rng.random_range(0..values)).collect();
+                    let mut expected = a.clone();
+                    expected.sort();
+                    for i in 0..n as usize {
+                        let mut b = a.clone();
+                        select_fn(&mut b, i);
+                        check_selected(&b, i, &expected);
+                    }
+                }
+            }
+        }
+
+        // Selecting the minimum of each suffix is selection sort.
+        ch02::tests::sort_i32(|a| for i in 0..a.len() { randomized_select(&mut a[i..], 0); });
+        ch02::tests::sort_i32(|a| for i in 0..a.len() { select(&mut a[i..], 0); });
+    }
+
+    #[test]
+    fn select_by_test() {
+        let mut a = ["pear", "fig", "banana", "apple", "kiwi"];
+        assert_eq!(*randomized_select_by(&mut a, 0, |x, y| x.len().cmp(&y.len())), "fig");
+        assert_eq!(*select_by(&mut a, 4, |x, y| x.len().cmp(&y.len())), "banana");
+        assert_eq!(*select(&mut [0.5, -1.0, 2.0], 1), 0.5);
+    }
+
+    #[test]
+    fn select_linear_test() {
+        // The worst-case number of comparisons of SELECT is linear, whatever the input.
+        let mut rng = rand::rng();
+        let n = 1 << 14;
+        let mut shuffled: Vec<u32> = (0..n).collect();
+        shuffled.shuffle(&mut rng);
+        let cases: [Vec<u32>; 4] = [shuffled, (0..n).collect(), (0..n).rev().collect(), vec![1; n as usize]];
+        for a in cases {
+            for i in [0, n as usize / 2, n as usize - 1] {
+                let mut b = a.clone();
+                let mut comparisons = 0;
+                select_by(&mut b, i, |x, y| {
+                    comparisons += 1;
+                    x.cmp(y)
+                });
+                let mut expected = a.clone();
+                expected.sort();
+                assert_eq!(b[i], expected[i]);
+                assert!(comparisons <= 30 * n as usize, "{comparisons} comparisons");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "order statistic 3 is out of range for length 3")]
+    fn select_out_of_range_test() {
+        select(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "order statistic 0 is out of range for length 0")]
+    fn randomized_select_empty_test() {
+        randomized_select::<i32>(&mut [], 0);
+    }
+}
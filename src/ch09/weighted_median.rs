@@ -0,0 +1,132 @@
+use std::mem;
+use crate::ch09::select_by;
+use crate::utils;
+
+/*
+    Weighted Median (Problem 9-2)
+    For distinct elements x_1, ..., x_n with positive weights w_1, ..., w_n, the weighted (lower) median is
+    the element x_k such that the total weight of the elements less than x_k is less than W/2, and the total weight
+    of the elements greater than x_k is at most W/2, where W is the total weight.
+    Equivalently, x_k is the first element in sorted order whose cumulative weight reaches W/2.
+
+    Select the median of the remaining elements with the worst-case linear `select`, and compare the weight below it
+    with W/2 to decide whether it is the answer or which half to keep. The halves shrink geometrically,
+    so the whole search takes Θ(n) worst-case time.
+*/
+pub fn weighted_median<'a, T: PartialOrd>(values: &'a [T], weights: &[f64]) -> &'a T {
+    assert_eq!(values.len(), weights.len(), "mismatched length");
+    assert!(!values.is_empty(), "cannot take the weighted median of an empty slice");
+    assert!(weights.iter().all(|&w| w >= 0.0), "weights must be non-negative");
+
+    let half = weights.iter().sum::<f64>() / 2.0;
+    let mut items: Vec<(&T, f64)> = values.iter().zip(weights.iter().copied()).collect();
+    let mut a = &mut items[..];
+    // The total weight of the elements below `a`, which have been discarded.
+    let mut below = 0.0;
+    loop {
+        let m = (a.len() - 1) / 2;
+        select_by(a, m, |x, y| utils::partial_compare(x.0, y.0));
+        let left = below + a[..m].iter().map(|&(_, w)| w).sum::<f64>();
+        if left >= half && m > 0 {
+            a = &mut mem::take(&mut a)[..m];
+        } else if left + a[m].1 >= half || m + 1 == a.len() {
+            return a[m].0;
+        } else {
+            below = left + a[m].1;
+            a = &mut mem::take(&mut a)[m + 1..];
+        }
+    }
+}
+
+// Post-office location problem (Problem 9-2 (d)): the point `p` minimizing `Σ w_i·|p - p_i|` is the weighted median.
+pub fn post_office_location(points: &[f64], weights: &[f64]) -> f64 {
+    *weighted_median(points, weights)
+}
+
+// 2-dimensional post-office location problem with the Manhattan distance `|x - x_i| + |y - y_i|` (Problem 9-2 (e)).
+// The distance separates into `x` and `y`, so each coordinate is the weighted median of its own coordinates.
+pub fn post_office_location_2d(points: &[(f64, f64)], weights: &[f64]) -> (f64, f64) {
+    let xs: Vec<f64> = points.iter().map(|p| p.0).collect();
+    let ys: Vec<f64> = points.iter().map(|p| p.1).collect();
+    (post_office_location(&xs, weights), post_office_location(&ys, weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    // The lower weighted median by sorting, for comparison.
+    fn weighted_median_by_sorting(values: &[u32], weights: &[f64]) -> u32 {
+        let mut items: Vec<(u32, f64)> = values.iter().copied().zip(weights.iter().copied()).collect();
+        items.sort_by_key(|&(x, _)| x);
+        let half = weights.iter().sum::<f64>() / 2.0;
+        let mut cumulative = 0.0;
+        for (x, w) in items {
+            cumulative += w;
+            if cumulative >= half {
+                return x;
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn weighted_median_test() {
+        assert_eq!(*weighted_median(&[7], &[1.0]), 7);
+        assert_eq!(*weighted_median(&[1, 2, 3, 4, 5], &[0.2; 5]), 3);
+        assert_eq!(*weighted_median(&[1, 2, 3, 4, 5], &[0.1, 0.1, 0.1, 0.1, 0.6]), 5);
+        assert_eq!(*weighted_median(&[5, 4, 3, 2, 1], &[0.1, 0.1, 0.1, 0.1, 0.6]), 1);
+        assert_eq!(*weighted_median(&[1, 2], &[0.5, 0.5]), 1);
+        assert_eq!(*weighted_median(&["b", "a", "c"], &[1.0, 1.0, 3.0]), "c");
+        assert_eq!(*weighted_median(&[3, 1, 2], &[0.0, 0.0, 0.0]), 1);
+
+        let mut rng = rand::rng();
+        for n in 1..100 {
+            let values: Vec<u32> = (0..n).map(|_| rng.random_range(0..30)).collect();
+            let weights: Vec<f64> = (0..n).map(|_| rng.random_range(0..4) as f64).collect();
+            assert_eq!(*weighted_median(&values, &weights), weighted_median_by_sorting(&values, &weights));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "mismatched length")]
+    fn weighted_median_length_test() {
+        weighted_median(&[1, 2], &[1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights must be non-negative")]
+    fn weighted_median_negative_test() {
+        weighted_median(&[1, 2], &[1.0, -1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot take the weighted median of an empty slice")]
+    fn weighted_median_empty_test() {
+        weighted_median::<i32>(&[], &[]);
+    }
+
+    #[test]
+    fn post_office_location_test() {
+        let cost = |p: f64, points: &[f64], weights: &[f64]| -> f64 {
+            points.iter().zip(weights).map(|(x, w)| w * (p - x).abs()).sum()
+        };
+
+        let mut rng = rand::rng();
+        for n in 1..50 {
+            let points: Vec<f64> = (0..n).map(|_| rng.random_range(-100.0..100.0)).collect();
+            let weights: Vec<f64> = (0..n).map(|_| rng.random()).collect();
+            let p = post_office_location(&points, &weights);
+            let best = cost(p, &points, &weights);
+            // The optimum of a piecewise linear function lies at one of the points.
+            for &q in &points {
+                assert!(best <= cost(q, &points, &weights) + 1e-9);
+            }
+        }
+
+        let points = [(0.0, 0.0), (4.0, 1.0), (1.0, 5.0), (2.0, 2.0)];
+        assert_eq!(post_office_location_2d(&points, &[1.0, 1.0, 1.0, 3.0]), (2.0, 2.0));
+        assert_eq!(post_office_location_2d(&points, &[1.0, 5.0, 1.0, 1.0]), (4.0, 1.0));
+    }
+}
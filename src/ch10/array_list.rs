@@ -0,0 +1,377 @@
+use std::iter;
+use std::marker::PhantomData;
+use std::mem;
+use crate::ch10::ContainerError;
+
+/*
+    Implementing Pointers and Objects (section 10.3 of the 3rd edition)
+    Without pointers, the objects of a doubly linked list are stored in arrays, and a pointer is an index.
+    The multiple-array representation keeps each attribute in its own array `key`, `next` and `prev`,
+    while the single-array representation keeps the attributes of an object in consecutive words of one array.
+
+    The unused objects form the free list, a singly linked list threaded through their `next` attributes:
+    ALLOCATE-OBJECT takes the head of the free list, and FREE-OBJECT pushes an object back onto it.
+*/
+// Storage of a fixed number of list objects, each with a key and `next` and `prev` pointers.
+// A free object has no key. The accessors may panic if `x` is not a pointer to an object.
+pub trait ObjectStore<T> {
+    fn with_capacity(capacity: usize) -> Self;
+
+    fn capacity(&self) -> usize;
+
+    // Pointer to the `i`-th object, where `i < capacity`.
+    fn object(&self, i: usize) -> usize;
+
+    // Whether `x` is a pointer to an object, whether free or not.
+    fn is_object(&self, x: usize) -> bool;
+
+    fn key(&self, x: usize) -> Option<&T>;
+
+    fn replace_key(&mut self, x: usize, key: Option<T>) -> Option<T>;
+
+    fn next(&self, x: usize) -> Option<usize>;
+
+    fn set_next(&mut self, x: usize, next: Option<usize>);
+
+    fn prev(&self, x: usize) -> Option<usize>;
+
+    fn set_prev(&mut self, x: usize, prev: Option<usize>);
+}
+
+// Multiple-array representation, where object `x` is `key[x]`, `next[x]` and `prev[x]`.
+#[derive(Debug, Clone)]
+pub struct MultipleArrays<T> {
+    key: Vec<Option<T>>,
+    next: Vec<Option<usize>>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<T> ObjectStore<T> for MultipleArrays<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        MultipleArrays { key: (0..capacity).map(|_| None).collect(), next: vec![None; capacity], prev: vec![None; capacity] }
+    }
+
+    fn capacity(&self) -> usize {
+        self.key.len()
+    }
+
+    fn object(&self, i: usize) -> usize {
+        i
+    }
+
+    fn is_object(&self, x: usize) -> bool {
+        x < self.capacity()
+    }
+
+    fn key(&self, x: usize) -> Option<&T> {
+        self.key[x].as_ref()
+    }
+
+    fn replace_key(&mut self, x: usize, key: Option<T>) -> Option<T> {
+        mem::replace(&mut self.key[x], key)
+    }
+
+    fn next(&self, x: usize) -> Option<usize> {
+        self.next[x]
+    }
+
+    fn set_next(&mut self, x: usize, next: Option<usize>) {
+        self.next[x] = next;
+    }
+
+    fn prev(&self, x: usize) -> Option<usize> {
+        self.prev[x]
+    }
+
+    fn set_prev(&mut self, x: usize, prev: Option<usize>) {
+        self.prev[x] = prev;
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Word<T> {
+    Key(Option<T>),
+    Pointer(Option<usize>),
+}
+
+// Single-array representation, where object `x` is `a[x]` (key), `a[x + 1]` (next) and `a[x + 2]` (prev),
+// so every pointer is a multiple of 3.
+#[derive(Debug, Clone)]
+pub struct SingleArray<T> {
+    a: Vec<Word<T>>,
+}
+
+impl<T> SingleArray<T> {
+    fn pointer(&self, i: usize) -> Option<usize> {
+        match self.a[i] {
+            Word::Pointer(p) => p,
+            Word::Key(_) => panic!("word {i} is not a pointer"),
+        }
+    }
+}
+
+impl<T> ObjectStore<T> for SingleArray<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        let a = (0..3 * capacity).map(|i| if i % 3 == 0 { Word::Key(None) } else { Word::Pointer(None) }).collect();
+        SingleArray { a }
+    }
+
+    fn capacity(&self) -> usize {
+        self.a.len() / 3
+    }
+
+    fn object(&self, i: usize) -> usize {
+        3 * i
+    }
+
+    fn is_object(&self, x: usize) -> bool {
+        x < self.a.len() && x.is_multiple_of(3)
+    }
+
+    fn key(&self, x: usize) -> Option<&T> {
+        match &self.a[x] {
+            Word::Key(key) => key.as_ref(),
+            Word::Pointer(_) => panic!("word {x} is not a key"),
+        }
+    }
+
+    fn replace_key(&mut self, x: usize, key: Option<T>) -> Option<T> {
+        match mem::replace(&mut self.a[x], Word::Key(key)) {
+            Word::Key(key) => key,
+            Word::Pointer(_) => panic!("word {x} is not a key"),
+        }
+    }
+
+    fn next(&self, x: usize) -> Option<usize> {
+        self.pointer(x + 1)
+    }
+
+    fn set_next(&mut self, x: usize, next: Option<usize>) {
+        self.a[x + 1] = Word::Pointer(next);
+    }
+
+    fn prev(&self, x: usize) -> Option<usize> {
+        self.pointer(x + 2)
+    }
+
+    fn set_prev(&mut self, x: usize, prev: Option<usize>) {
+        self.a[x + 2] = Word::Pointer(prev);
+    }
+}
+
+// Doubly linked list without a sentinel (section 10.2), whose objects are allocated from an `ObjectStore`.
+// An object is addressed by its pointer, which stays valid until the object is deleted.
+#[derive(Debug, Clone)]
+pub struct ArrayList<T, S> {
+    store: S,
+    head: Option<usize>,
+    free: Option<usize>,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+pub type MultipleArrayList<T> = ArrayList<T, MultipleArrays<T>>;
+pub type SingleArrayList<T> = ArrayList<T, SingleArray<T>>;
+
+impl<T, S: ObjectStore<T>> ArrayList<T, S> {
+    pub fn new(capacity: usize) -> Self {
+        let mut store = S::with_capacity(capacity);
+        for i in 0..capacity {
+            let next = if i + 1 < capacity { Some(store.object(i + 1)) } else { None };
+            let x = store.object(i);
+            store.set_next(x, next);
+        }
+        let free = if capacity > 0 { Some(store.object(0)) } else { None };
+        ArrayList { store, head: None, free, len: 0, marker: PhantomData }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.store.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.free.is_none()
+    }
+
+    pub fn head(&self) -> Option<usize> {
+        self.head
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    // Return the key of object `x`, or `None` if `x` is free or is not a pointer to an object.
+    pub fn key(&self, x: usize) -> Option<&T> {
+        if !self.store.is_object(x) { return None; }
+        self.store.key(x)
+    }
+
+    pub fn next(&self, x: usize) -> Option<usize> {
+        self.key(x).and_then(|_| self.store.next(x))
+    }
+
+    pub fn prev(&self, x: usize) -> Option<usize> {
+        self.key(x).and_then(|_| self.store.prev(x))
+    }
+
+    // LIST-SEARCH, which returns the first object with key `k`.
+    pub fn search(&self, k: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let mut x = self.head;
+        while let Some(y) = x {
+            if self.store.key(y) == Some(k) { return Some(y); }
+            x = self.store.next(y);
+        }
+        None
+    }
+
+    // LIST-PREPEND, which fails with `Overflow` if every object is in use.
+    pub fn insert(&mut self, key: T) -> Result<usize, ContainerError> {
+        let x = self.allocate_object()?;
+        self.store.replace_key(x, Some(key));
+        self.store.set_next(x, self.head);
+        self.store.set_prev(x, None);
+        if let Some(head) = self.head {
+            self.store.set_prev(head, Some(x));
+        }
+        self.head = Some(x);
+        self.len += 1;
+        Ok(x)
+    }
+
+    // LIST-DELETE, returning `None` if `x` is free or is not a pointer to an object.
+    pub fn delete(&mut self, x: usize) -> Option<T> {
+        self.key(x)?;
+        let (prev, next) = (self.store.prev(x), self.store.next(x));
+        match prev {
+            Some(prev) => self.store.set_next(prev, next),
+            None => self.head = next,
+        }
+        if let Some(next) = next {
+            self.store.set_prev(next, prev);
+        }
+        self.len -= 1;
+        let key = self.store.replace_key(x, None);
+        self.free_object(x);
+        key
+    }
+
+    // Iterate from the head to the tail.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut x = self.head;
+        iter::from_fn(move || {
+            let y = x?;
+            x = self.store.next(y);
+            self.store.key(y)
+        })
+    }
+
+    // ALLOCATE-OBJECT
+    fn allocate_object(&mut self) -> Result<usize, ContainerError> {
+        let x = self.free.ok_or(ContainerError::Overflow)?;
+        self.free = self.store.next(x);
+        Ok(x)
+    }
+
+    // FREE-OBJECT
+    fn free_object(&mut self, x: usize) {
+        self.store.set_next(x, self.free);
+        self.store.set_prev(x, None);
+        self.free = Some(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use rand::Rng;
+    use super::*;
+
+    fn array_list<S: ObjectStore<i32>>() {
+        let mut list = ArrayList::<i32, S>::new(4);
+        let x = list.insert(4).unwrap();
+        list.insert(1).unwrap();
+        list.insert(16).unwrap();
+        list.insert(9).unwrap();
+        assert!(list.is_full());
+        assert_eq!(list.insert(25), Err(ContainerError::Overflow));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [9, 16, 1, 4]);
+
+        assert_eq!(list.delete(x), Some(4));
+        assert_eq!(list.delete(x), None);
+        assert_eq!(list.key(x), None);
+        // The object freed last is allocated first.
+        assert_eq!(list.insert(25), Ok(x));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [25, 9, 16, 1]);
+
+        let y = list.search(&16).unwrap();
+        assert_eq!(list.key(y), Some(&16));
+        assert_eq!(list.prev(y).and_then(|p| list.key(p)), Some(&9));
+        assert_eq!(list.next(y).and_then(|n| list.key(n)), Some(&1));
+        assert_eq!(list.search(&36), None);
+        assert_eq!(list.delete(y), Some(16));
+        assert_eq!(list.delete(list.head().unwrap()), Some(25));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [9, 1]);
+        assert_eq!((list.len(), list.capacity()), (2, 4));
+
+        // A pointer past the last object is not an object either.
+        let past = list.store().object(list.capacity());
+        assert_eq!((list.key(past), list.next(past), list.prev(past)), (None, None, None));
+        assert_eq!(list.delete(past), None);
+
+        let mut empty = ArrayList::<i32, S>::new(0);
+        assert_eq!(empty.insert(1), Err(ContainerError::Overflow));
+        assert!(empty.is_empty());
+    }
+
+    fn array_list_random<S: ObjectStore<i32>>() {
+        let mut rng = rand::rng();
+        let capacity = 8;
+        let mut list = ArrayList::<i32, S>::new(capacity);
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            if rng.random_bool(0.5) {
+                let expected = if model.len() < capacity { model.push_front(i); true } else { false };
+                assert_eq!(list.insert(i).is_ok(), expected);
+            } else if let Some(&k) = model.get(rng.random_range(0..model.len().max(1))) {
+                let x = list.search(&k).unwrap();
+                assert_eq!(list.delete(x), Some(k));
+                model.retain(|&j| j != k);
+            }
+            assert_eq!(list.len(), model.len());
+            assert!(list.iter().eq(model.iter()));
+        }
+    }
+
+    #[test]
+    fn multiple_array_list_test() {
+        array_list::<MultipleArrays<i32>>();
+        array_list_random::<MultipleArrays<i32>>();
+    }
+
+    #[test]
+    fn single_array_list_test() {
+        array_list::<SingleArray<i32>>();
+        array_list_random::<SingleArray<i32>>();
+
+        let mut list = SingleArrayList::new(3);
+        let pointers: Vec<usize> = (0..3).map(|i| list.insert(i).unwrap()).collect();
+        assert_eq!(pointers, [0, 3, 6]);
+        assert_eq!(list.store().capacity(), 3);
+        // Pointers to the `next` and `prev` words of an object do not point to an object.
+        assert_eq!((list.key(4), list.next(4), list.prev(5)), (None, None, None));
+        assert_eq!(list.delete(5), None);
+        assert_eq!(list.len(), 3);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::ch10::ContainerError;
+
+// Double-ended queue of at most `capacity` elements stored in a circular array (Exercise 10.1-5).
+// The elements are `a[head]`, `a[head + 1]`, ..., wrapping around to `a[0]` after the last slot.
+// Counting the elements in `len`, instead of leaving one slot empty as ENQUEUE does, tells a full queue from an empty one.
+#[derive(Debug, Clone)]
+pub struct Deque<T> {
+    a: Box<[Option<T>]>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+    pub fn new(capacity: usize) -> Self {
+        Deque { a: (0..capacity).map(|_| None).collect(), head: 0, len: 0 }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.a.len()
+    }
+
+    // Return the `i`-th element from the front.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len { return None; }
+        self.a[self.index(i)].as_ref()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    pub fn push_back(&mut self, x: T) -> Result<(), ContainerError> {
+        if self.is_full() { return Err(ContainerError::Overflow); }
+        let tail = self.index(self.len);
+        self.a[tail] = Some(x);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, x: T) -> Result<(), ContainerError> {
+        if self.is_full() { return Err(ContainerError::Overflow); }
+        self.head = self.index(self.a.len() - 1);
+        self.a[self.head] = Some(x);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Result<T, ContainerError> {
+        if self.is_empty() { return Err(ContainerError::Underflow); }
+        let x = self.a[self.head].take().unwrap();
+        self.head = self.index(1);
+        self.len -= 1;
+        Ok(x)
+    }
+
+    pub fn pop_back(&mut self) -> Result<T, ContainerError> {
+        if self.is_empty() { return Err(ContainerError::Underflow); }
+        self.len -= 1;
+        let tail = self.index(self.len);
+        Ok(self.a[tail].take().unwrap())
+    }
+
+    // Iterate from the front to the back.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        (0..self.len).map(|i| self.a[self.index(i)].as_ref().unwrap())
+    }
+
+    // Index into `a` of the `i`-th slot from `head`, where `i < capacity`.
+    fn index(&self, i: usize) -> usize {
+        (self.head + i) % self.a.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn deque_test() {
+        let mut d = Deque::new(4);
+        assert_eq!(d.front(), None);
+        assert_eq!(d.back(), None);
+        d.push_back(2).unwrap();
+        d.push_front(1).unwrap();
+        d.push_back(3).unwrap();
+        d.push_front(0).unwrap();
+        assert!(d.is_full());
+        assert_eq!(d.push_back(4), Err(ContainerError::Overflow));
+        assert_eq!(d.push_front(4), Err(ContainerError::Overflow));
+        assert_eq!(d.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(d.iter().rev().copied().collect::<Vec<_>>(), [3, 2, 1, 0]);
+        assert_eq!((d.front(), d.back(), d.get(2), d.get(4)), (Some(&0), Some(&3), Some(&2), None));
+
+        assert_eq!(d.pop_back(), Ok(3));
+        assert_eq!(d.pop_front(), Ok(0));
+        assert_eq!(d.pop_front(), Ok(1));
+        assert_eq!(d.pop_back(), Ok(2));
+        assert_eq!(d.pop_back(), Err(ContainerError::Underflow));
+        assert_eq!(d.pop_front(), Err(ContainerError::Underflow));
+
+        let mut d = Deque::new(0);
+        assert_eq!(d.push_front(1), Err(ContainerError::Overflow));
+        assert_eq!(d.pop_back(), Err(ContainerError::Underflow));
+    }
+
+    #[test]
+    fn deque_random_test() {
+        let mut rng = rand::rng();
+        let capacity = 5;
+        let mut d = Deque::new(capacity);
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            match rng.random_range(0..4) {
+                0 => {
+                    let expected = if model.len() < capacity { model.push_back(i); Ok(()) } else { Err(ContainerError::Overflow) };
+                    assert_eq!(d.push_back(i), expected);
+                }
+                1 => {
+                    let expected = if model.len() < capacity { model.push_front(i); Ok(()) } else { Err(ContainerError::Overflow) };
+                    assert_eq!(d.push_front(i), expected);
+                }
+                2 => assert_eq!(d.pop_front(), model.pop_front().ok_or(ContainerError::Underflow)),
+                _ => assert_eq!(d.pop_back(), model.pop_back().ok_or(ContainerError::Underflow)),
+            }
+            assert_eq!(d.len(), model.len());
+            assert!(d.iter().eq(model.iter()));
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt;
+
+// Error of an operation on a fixed-capacity container, which the book reports as "overflow" or "underflow".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerError {
+    // Inserting into a full container
+    Overflow,
+    // Removing from an empty container
+    Underflow,
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerError::Overflow => write!(f, "overflow"),
+            ContainerError::Underflow => write!(f, "underflow"),
+        }
+    }
+}
+
+impl Error for ContainerError {}
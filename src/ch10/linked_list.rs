@@ -0,0 +1,294 @@
+// Handle to a node of a `LinkedList`, made of the index of the node and the generation of its slot.
+// Deleting a node bumps the generation before its slot is reused, so the list rejects a handle to a deleted node
+// instead of reading whichever node has taken its place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    index: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    key: Option<T>, // `None` for the sentinel and for free nodes
+    prev: usize,
+    next: usize,
+    generation: u64,
+}
+
+// Doubly linked list with a sentinel (section 10.2), whose nodes are stored in a vector and linked by index.
+// `nodes[NIL]` is the sentinel `L.nil`, so `L.nil.next` is the head and `L.nil.prev` is the tail,
+// and deleted nodes are kept in a free list to be reused by later insertions.
+#[derive(Debug, Clone)]
+pub struct LinkedList<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+const NIL: usize = 0;
+
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList { nodes: vec![Node { key: None, prev: NIL, next: NIL, generation: 0 }], free: Vec::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.nodes[self.nodes[NIL].next].key.as_ref()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.nodes[self.nodes[NIL].prev].key.as_ref()
+    }
+
+    pub fn contains(&self, h: NodeHandle) -> bool {
+        self.node(h).is_some()
+    }
+
+    pub fn get(&self, h: NodeHandle) -> Option<&T> {
+        self.node(h)?;
+        self.nodes[h.index].key.as_ref()
+    }
+
+    pub fn get_mut(&mut self, h: NodeHandle) -> Option<&mut T> {
+        self.node(h)?;
+        self.nodes[h.index].key.as_mut()
+    }
+
+    // LIST-SEARCH, which returns the first node with key `k`.
+    pub fn search(&self, k: &T) -> Option<NodeHandle>
+    where
+        T: PartialEq,
+    {
+        let mut x = self.nodes[NIL].next;
+        while x != NIL && self.nodes[x].key.as_ref() != Some(k) {
+            x = self.nodes[x].next;
+        }
+        if x == NIL { return None; }
+        Some(NodeHandle { index: x, generation: self.nodes[x].generation })
+    }
+
+    // LIST-PREPEND, which inserts `key` at the head.
+    pub fn prepend(&mut self, key: T) -> NodeHandle {
+        self.insert_after_index(NIL, key)
+    }
+
+    // Insert `key` at the tail.
+    pub fn append(&mut self, key: T) -> NodeHandle {
+        let tail = self.nodes[NIL].prev;
+        self.insert_after_index(tail, key)
+    }
+
+    // LIST-INSERT, which inserts `key` immediately following the node of `h`.
+    pub fn insert_after(&mut self, h: NodeHandle, key: T) -> NodeHandle {
+        let y = self.node(h).expect("invalid handle");
+        self.insert_after_index(y, key)
+    }
+
+    // LIST-DELETE, returning `None` if `h` is no longer valid.
+    pub fn delete(&mut self, h: NodeHandle) -> Option<T> {
+        let x = self.node(h)?;
+        Some(self.delete_index(x))
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.nodes[NIL].next;
+        if head == NIL { return None; }
+        Some(self.delete_index(head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.nodes[NIL].prev;
+        if tail == NIL { return None; }
+        Some(self.delete_index(tail))
+    }
+
+    // Iterate from the head to the tail.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { list: self, front: self.nodes[NIL].next, back: self.nodes[NIL].prev, len: self.len }
+    }
+
+    fn node(&self, h: NodeHandle) -> Option<usize> {
+        let node = self.nodes.get(h.index)?;
+        if h.index == NIL || node.generation != h.generation || node.key.is_none() { return None; }
+        Some(h.index)
+    }
+
+    fn insert_after_index(&mut self, y: usize, key: T) -> NodeHandle {
+        let next = self.nodes[y].next;
+        let x = match self.free.pop() {
+            Some(x) => {
+                let node = &mut self.nodes[x];
+                node.key = Some(key);
+                node.prev = y;
+                node.next = next;
+                x
+            }
+            None => {
+                self.nodes.push(Node { key: Some(key), prev: y, next, generation: 0 });
+                self.nodes.len() - 1
+            }
+        };
+        self.nodes[next].prev = x;
+        self.nodes[y].next = x;
+        self.len += 1;
+        NodeHandle { index: x, generation: self.nodes[x].generation }
+    }
+
+    fn delete_index(&mut self, x: usize) -> T {
+        let Node { prev, next, .. } = self.nodes[x];
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        let node = &mut self.nodes[x];
+        node.generation += 1;
+        self.free.push(x);
+        self.len -= 1;
+        node.key.take().unwrap()
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Iterator over the keys of a `LinkedList`, from either end.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    list: &'a LinkedList<T>,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 { return None; }
+        let node = &self.list.nodes[self.front];
+        self.front = node.next;
+        self.len -= 1;
+        node.key.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 { return None; }
+        let node = &self.list.nodes[self.back];
+        self.back = node.prev;
+        self.len -= 1;
+        node.key.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn linked_list_test() {
+        // Figure 10.4
+        let mut list = LinkedList::new();
+        for key in [1, 4, 16, 9] {
+            list.prepend(key);
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [9, 16, 4, 1]);
+        list.prepend(25);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [25, 9, 16, 4, 1]);
+        let h = list.search(&1).unwrap();
+        assert_eq!(list.delete(h), Some(1));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [25, 9, 16, 4]);
+        let h = list.search(&4).unwrap();
+        assert_eq!(list.delete(h), Some(4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [25, 9, 16]);
+
+        let h = list.search(&9).unwrap();
+        list.insert_after(h, 36);
+        list.append(49);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [49, 16, 36, 9, 25]);
+        assert_eq!((list.front(), list.back(), list.len()), (Some(&25), Some(&49), 5));
+        assert_eq!(list.search(&100), None);
+
+        *list.get_mut(h).unwrap() = 81;
+        assert_eq!(list.get(h), Some(&81));
+        assert_eq!(list.pop_front(), Some(25));
+        assert_eq!(list.pop_back(), Some(49));
+        assert_eq!((&list).into_iter().copied().collect::<Vec<_>>(), [81, 36, 16]);
+    }
+
+    #[test]
+    fn linked_list_handle_test() {
+        let mut list = LinkedList::new();
+        let a = list.append("a");
+        assert_eq!(list.delete(a), Some("a"));
+        assert!(!list.contains(a));
+        assert_eq!(list.delete(a), None);
+
+        // The freed node is reused, but the stale handle does not refer to it.
+        let b = list.append("b");
+        assert_eq!(list.nodes.len(), 2);
+        assert_eq!(list.get(a), None);
+        assert_eq!(list.get(b), Some(&"b"));
+        assert!(list.pop_front().is_some() && list.pop_front().is_none() && list.pop_back().is_none());
+        assert_eq!((list.front(), list.back()), (None, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid handle")]
+    fn linked_list_insert_after_stale_test() {
+        let mut list = LinkedList::new();
+        let h = list.append(1);
+        list.delete(h);
+        list.insert_after(h, 2);
+    }
+
+    #[test]
+    fn linked_list_random_test() {
+        let mut rng = rand::rng();
+        let mut list = LinkedList::new();
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            match rng.random_range(0..5) {
+                0 => { list.prepend(i); model.push_front(i); }
+                1 => { list.append(i); model.push_back(i); }
+                2 => assert_eq!(list.pop_front(), model.pop_front()),
+                3 => assert_eq!(list.pop_back(), model.pop_back()),
+                _ => if let Some(&k) = model.get(model.len() / 2) {
+                    let h = list.search(&k).unwrap();
+                    assert_eq!(list.delete(h), Some(k));
+                    model.remove(model.len() / 2);
+                }
+            }
+            assert_eq!(list.len(), model.len());
+            assert!(list.iter().eq(model.iter()));
+            assert!(list.iter().rev().eq(model.iter().rev()));
+        }
+    }
+}
@@ -0,0 +1,13 @@
+mod error;
+mod stack;
+mod deque;
+mod queue;
+mod linked_list;
+mod array_list;
+
+pub use error::*;
+pub use stack::*;
+pub use deque::*;
+pub use queue::*;
+pub use linked_list::*;
+pub use array_list::*;
\ No newline at end of file
@@ -0,0 +1,83 @@
+use crate::ch10::{ContainerError, Deque};
+
+// Queue of at most `capacity` elements stored in a circular array (section 10.1),
+// which is a `Deque` that only inserts at the tail and removes from the head.
+#[derive(Debug, Clone)]
+pub struct Queue<T> {
+    deque: Deque<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new(capacity: usize) -> Self {
+        Queue { deque: Deque::new(capacity) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.deque.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.deque.is_full()
+    }
+
+    // Return the head element without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.deque.front()
+    }
+
+    // ENQUEUE, which fails with `Overflow` if the queue is full.
+    pub fn enqueue(&mut self, x: T) -> Result<(), ContainerError> {
+        self.deque.push_back(x)
+    }
+
+    // DEQUEUE, which fails with `Underflow` if the queue is empty.
+    pub fn dequeue(&mut self) -> Result<T, ContainerError> {
+        self.deque.pop_front()
+    }
+
+    // Iterate from the head to the tail.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.deque.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_test() {
+        // Figure 10.3, with a queue of 12 slots whose head starts at the 7th
+        let mut q = Queue::new(12);
+        for x in 0..6 {
+            q.enqueue(x).unwrap();
+            q.dequeue().unwrap();
+        }
+        for x in [15, 6, 9, 8, 4] {
+            q.enqueue(x).unwrap();
+        }
+        for x in [17, 3, 5] {
+            q.enqueue(x).unwrap();
+        }
+        assert_eq!(q.dequeue(), Ok(15));
+        assert_eq!(q.peek(), Some(&6));
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), [6, 9, 8, 4, 17, 3, 5]);
+
+        while q.len() < q.capacity() {
+            q.enqueue(0).unwrap();
+        }
+        assert!(q.is_full());
+        assert_eq!(q.enqueue(1), Err(ContainerError::Overflow));
+        while q.dequeue().is_ok() {}
+        assert!(q.is_empty());
+        assert_eq!(q.dequeue(), Err(ContainerError::Underflow));
+    }
+}
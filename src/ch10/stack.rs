@@ -0,0 +1,92 @@
+use crate::ch10::ContainerError;
+
+// Stack of at most `capacity` elements stored in an array (section 10.1), where `len` plays the role of `S.top`.
+#[derive(Debug, Clone)]
+pub struct Stack<T> {
+    a: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> Stack<T> {
+    pub fn new(capacity: usize) -> Self {
+        Stack { a: Vec::with_capacity(capacity), capacity }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    // STACK-EMPTY
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.a.len() == self.capacity
+    }
+
+    // Return the top element without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.a.last()
+    }
+
+    // PUSH, which fails with `Overflow` if the stack is full.
+    pub fn push(&mut self, x: T) -> Result<(), ContainerError> {
+        if self.is_full() { return Err(ContainerError::Overflow); }
+        self.a.push(x);
+        Ok(())
+    }
+
+    // POP, which fails with `Underflow` if the stack is empty.
+    pub fn pop(&mut self) -> Result<T, ContainerError> {
+        self.a.pop().ok_or(ContainerError::Underflow)
+    }
+
+    // Iterate from the bottom to the top of the stack.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.a.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_test() {
+        // Figure 10.2
+        let mut s = Stack::new(7);
+        for x in [15, 6, 2, 9] {
+            s.push(x).unwrap();
+        }
+        s.push(17).unwrap();
+        s.push(3).unwrap();
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), [15, 6, 2, 9, 17, 3]);
+        assert_eq!(s.pop(), Ok(3));
+        assert_eq!(s.peek(), Some(&17));
+        assert_eq!(s.len(), 5);
+
+        s.push(1).unwrap();
+        s.push(2).unwrap();
+        assert!(s.is_full());
+        assert_eq!(s.push(4), Err(ContainerError::Overflow));
+        while s.pop().is_ok() {}
+        assert!(s.is_empty());
+        assert_eq!(s.pop(), Err(ContainerError::Underflow));
+        assert_eq!(s.peek(), None);
+    }
+
+    #[test]
+    fn stack_zero_capacity_test() {
+        let mut s = Stack::new(0);
+        assert!(s.is_empty() && s.is_full());
+        assert_eq!(s.push("a"), Err(ContainerError::Overflow));
+        assert_eq!(s.pop(), Err(ContainerError::Underflow));
+        assert_eq!(ContainerError::Overflow.to_string(), "overflow");
+        assert_eq!(ContainerError::Underflow.to_string(), "underflow");
+    }
+}
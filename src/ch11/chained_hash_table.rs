@@ -0,0 +1,217 @@
+use std::cell::Cell;
+use std::hash::Hash;
+use std::mem;
+use crate::ch11::{natural_key, HashFamily, HashFunction, ProbeStats};
+
+// Hash table resolving collisions by chaining (section 11.2), where `table[j]` is the chain of the elements hashing to `j`.
+// Whenever an insertion would take the load factor α = n/m above `max_load_factor`, the table doubles
+// and draws a new hash function from its family, so with universal hashing each operation takes O(1) expected time.
+#[derive(Debug, Clone)]
+pub struct ChainedHashTable<K, V> {
+    table: Vec<Vec<(K, V)>>,
+    family: HashFamily,
+    h: HashFunction,
+    len: usize,
+    max_load_factor: f64,
+    stats: Cell<ProbeStats>,
+}
+
+impl<K: Hash + Eq, V> ChainedHashTable<K, V> {
+    pub fn new(family: HashFamily) -> Self {
+        Self::with_slots(family, 8)
+    }
+
+    // Create a table of at least `m` slots, rounded up to a size that suits `family`.
+    pub fn with_slots(family: HashFamily, m: usize) -> Self {
+        let m = family.table_size(m);
+        ChainedHashTable {
+            table: (0..m).map(|_| Vec::new()).collect(),
+            family,
+            h: family.function(m, &mut rand::rng()),
+            len: 0,
+            max_load_factor: 1.0,
+            stats: Cell::new(ProbeStats::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn slots(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots() as f64
+    }
+
+    pub fn max_load_factor(&self) -> f64 {
+        self.max_load_factor
+    }
+
+    // Set the load factor above which the table grows, where `f64::INFINITY` keeps the number of slots fixed.
+    pub fn set_max_load_factor(&mut self, max_load_factor: f64) {
+        assert!(max_load_factor > 0.0, "max load factor must be positive");
+        self.max_load_factor = max_load_factor;
+    }
+
+    pub fn hash_function(&self) -> HashFunction {
+        self.h
+    }
+
+    pub fn stats(&self) -> ProbeStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(ProbeStats::default());
+    }
+
+    // The length of each chain, in slot order.
+    pub fn chain_lengths(&self) -> impl Iterator<Item = usize> {
+        self.table.iter().map(Vec::len)
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+
+    // CHAINED-HASH-SEARCH
+    pub fn get(&self, k: &K) -> Option<&V> {
+        let (j, i) = self.search(k);
+        Some(&self.table[j][i?].1)
+    }
+
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        let (j, i) = self.search(k);
+        Some(&mut self.table[j][i?].1)
+    }
+
+    // CHAINED-HASH-INSERT, which replaces and returns the value of `k` if `k` is already in the table.
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        let (j, i) = self.search(&k);
+        if let Some(i) = i {
+            return Some(mem::replace(&mut self.table[j][i].1, v));
+        }
+        if (self.len + 1) as f64 > self.max_load_factor * self.slots() as f64 {
+            self.resize(self.family.table_size(2 * self.slots()));
+        }
+        let j = self.h.hash(natural_key(&k));
+        self.table[j].push((k, v));
+        self.len += 1;
+        None
+    }
+
+    // CHAINED-HASH-DELETE
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let (j, i) = self.search(k);
+        let (_, v) = self.table[j].swap_remove(i?);
+        self.len -= 1;
+        Some(v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.table.iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    // Return the slot of `k`, and the index of `k` in its chain if `k` is in the table.
+    fn search(&self, k: &K) -> (usize, Option<usize>) {
+        let j = self.h.hash(natural_key(k));
+        let i = self.table[j].iter().position(|(key, _)| key == k);
+        let mut stats = self.stats.get();
+        stats.record(i.map_or(self.table[j].len(), |i| i + 1) as u64);
+        self.stats.set(stats);
+        (j, i)
+    }
+
+    fn resize(&mut self, m: usize) {
+        let table = mem::replace(&mut self.table, (0..m).map(|_| Vec::new()).collect());
+        self.h = self.family.function(m, &mut rand::rng());
+        for (k, v) in table.into_iter().flatten() {
+            let j = self.h.hash(natural_key(&k));
+            self.table[j].push((k, v));
+        }
+        let mut stats = self.stats.get();
+        stats.resizes += 1;
+        self.stats.set(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use rand::Rng;
+    use super::*;
+
+    const FAMILIES: [HashFamily; 3] = [HashFamily::Division, HashFamily::Multiplication, HashFamily::Universal];
+
+    #[test]
+    fn chained_hash_table_test() {
+        for family in FAMILIES {
+            let mut t = ChainedHashTable::new(family);
+            assert!(t.is_empty());
+            assert_eq!(t.insert("apple", 1), None);
+            assert_eq!(t.insert("banana", 2), None);
+            assert_eq!(t.insert("apple", 3), Some(1));
+            assert_eq!(t.len(), 2);
+            assert_eq!(t.get(&"apple"), Some(&3));
+            *t.get_mut(&"banana").unwrap() += 10;
+            assert_eq!(t.get(&"banana"), Some(&12));
+            assert!(!t.contains_key(&"cherry"));
+            assert_eq!(t.remove(&"apple"), Some(3));
+            assert_eq!(t.remove(&"apple"), None);
+            assert_eq!(t.iter().collect::<Vec<_>>(), [(&"banana", &12)]);
+            assert_eq!(t.len(), 1);
+        }
+    }
+
+    #[test]
+    fn chained_hash_table_random_test() {
+        let mut rng = rand::rng();
+        for family in FAMILIES {
+            let mut t = ChainedHashTable::new(family);
+            let mut model = HashMap::new();
+            for i in 0..3000 {
+                let k: u32 = rng.random_range(0..500);
+                match rng.random_range(0..3) {
+                    0 | 1 => assert_eq!(t.insert(k, i), model.insert(k, i)),
+                    _ => assert_eq!(t.remove(&k), model.remove(&k)),
+                }
+                assert_eq!(t.len(), model.len());
+                assert!(t.load_factor() <= t.max_load_factor());
+            }
+            assert!(model.iter().all(|(k, v)| t.get(k) == Some(v)));
+            assert_eq!(t.chain_lengths().sum::<usize>(), t.len());
+            assert_eq!(t.slots(), t.hash_function().slots());
+        }
+    }
+
+    #[test]
+    fn chained_hash_table_stats_test() {
+        let mut t = ChainedHashTable::with_slots(HashFamily::Division, 7);
+        assert_eq!(t.slots(), 7);
+        t.set_max_load_factor(f64::INFINITY);
+        // Keys congruent modulo 7 all go into the same chain.
+        for k in 0..10u64 {
+            t.insert(7 * k, k);
+        }
+        assert_eq!(t.slots(), 7);
+        assert_eq!(t.chain_lengths().max(), Some(10));
+        t.reset_stats();
+        assert_eq!(t.get(&63), Some(&9));
+        assert_eq!(t.get(&70), None);
+        assert_eq!(t.stats(), ProbeStats { operations: 2, probes: 20, max_probes: 10, resizes: 0 });
+        assert_eq!(t.stats().average_probes(), 10.0);
+
+        t.set_max_load_factor(1.0);
+        t.insert(1, 1);
+        assert_eq!(t.slots(), 17);
+        assert_eq!(t.stats().resizes, 1);
+        assert!(t.load_factor() <= 1.0);
+    }
+}
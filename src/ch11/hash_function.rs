@@ -0,0 +1,217 @@
+use std::hash::{Hash, Hasher};
+use rand::Rng;
+
+/*
+    Hash Functions (section 11.3)
+    Hash functions map keys, interpreted as natural numbers, to the slots `0..m` of a table.
+    Any `Hash` key is interpreted as a natural number by `natural_key`, which keeps an integer key as it is
+    and folds a string or a compound key into 64 bits, so that the three methods below see the actual keys.
+*/
+// Return the key `k` interpreted as a natural number.
+pub fn natural_key<K: Hash + ?Sized>(k: &K) -> u64 {
+    let mut hasher = NaturalHasher(0);
+    k.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Hasher folding each integer written to it into the state by `state·P + x`, so a single integer is left unchanged.
+struct NaturalHasher(u64);
+
+impl NaturalHasher {
+    // The 64-bit FNV prime
+    const P: u64 = 0x100000001b3;
+
+    fn fold(&mut self, x: u64) {
+        self.0 = self.0.wrapping_mul(Self::P).wrapping_add(x);
+    }
+}
+
+impl Hasher for NaturalHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.fold(b as u64);
+        }
+    }
+
+    fn write_u8(&mut self, x: u8) {
+        self.fold(x as u64);
+    }
+
+    fn write_u16(&mut self, x: u16) {
+        self.fold(x as u64);
+    }
+
+    fn write_u32(&mut self, x: u32) {
+        self.fold(x as u64);
+    }
+
+    fn write_u64(&mut self, x: u64) {
+        self.fold(x);
+    }
+
+    fn write_usize(&mut self, x: usize) {
+        self.fold(x as u64);
+    }
+}
+
+// Method of hashing, from which a `ChainedHashTable` or an `OpenAddressingHashTable` draws a new `HashFunction` whenever it is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashFamily {
+    // h(k) = k mod m, with a prime m
+    Division,
+    // h(k) = (a·k mod 2^w) >> (w - ℓ), with m = 2^ℓ and a random odd a
+    Multiplication,
+    // h(k) = ((a·k + b) mod p) mod m, with random a and b
+    Universal,
+}
+
+impl HashFamily {
+    // Return the table size that suits this method, which is at least `min`:
+    // a prime for the division method, and a power of 2 otherwise.
+    pub fn table_size(self, min: usize) -> usize {
+        match self {
+            HashFamily::Division => next_prime(min),
+            HashFamily::Multiplication | HashFamily::Universal => min.max(1).next_power_of_two(),
+        }
+    }
+
+    // Draw a hash function into `m` slots, where `m` is a power of 2 for the multiplication method.
+    pub fn function<R: Rng>(self, m: usize, rng: &mut R) -> HashFunction {
+        match self {
+            HashFamily::Division => HashFunction::division(m),
+            HashFamily::Multiplication => {
+                assert!(m.is_power_of_two(), "table size {m} is not a power of 2");
+                HashFunction::multiplication(m.ilog2(), rng)
+            }
+            HashFamily::Universal => HashFunction::universal(m, rng),
+        }
+    }
+}
+
+// A hash function drawn from a `HashFamily`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashFunction {
+    Division { m: u64 },
+    Multiplication { a: u64, l: u32 },
+    Universal { a: u64, b: u64, m: u64 },
+}
+
+impl HashFunction {
+    // The Mersenne prime 2^61 - 1, which is the p of the universal family.
+    // Keys are reduced modulo p first, so the family is universal for the keys less than p.
+    pub const P: u64 = (1 << 61) - 1;
+
+    pub fn division(m: usize) -> Self {
+        assert!(m > 0, "table size must be positive");
+        HashFunction::Division { m: m as u64 }
+    }
+
+    // Multiply-shift into `2^l` slots (section 11.3.2).
+    pub fn multiplication<R: Rng>(l: u32, rng: &mut R) -> Self {
+        assert!(l < 64, "table size 2^{l} is too large");
+        HashFunction::Multiplication { a: rng.random::<u64>() | 1, l }
+    }
+
+    // A random member of the universal family H_pm (section 11.3.4).
+    pub fn universal<R: Rng>(m: usize, rng: &mut R) -> Self {
+        assert!(m > 0, "table size must be positive");
+        HashFunction::Universal { a: rng.random_range(1..Self::P), b: rng.random_range(0..Self::P), m: m as u64 }
+    }
+
+    // The number of slots `m`.
+    pub fn slots(&self) -> usize {
+        match *self {
+            HashFunction::Division { m } | HashFunction::Universal { m, .. } => m as usize,
+            HashFunction::Multiplication { l, .. } => 1 << l,
+        }
+    }
+
+    pub fn hash(&self, k: u64) -> usize {
+        match *self {
+            HashFunction::Division { m } => (k % m) as usize,
+            HashFunction::Multiplication { a, l } => k.wrapping_mul(a).checked_shr(64 - l).unwrap_or(0) as usize,
+            HashFunction::Universal { a, b, m } => {
+                let k = (k % Self::P) as u128;
+                ((a as u128 * k + b as u128) % Self::P as u128 % m as u128) as usize
+            }
+        }
+    }
+}
+
+// Return the smallest prime that is at least `n`.
+pub(super) fn next_prime(n: usize) -> usize {
+    let is_prime = |n: usize| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    (n..).find(|&n| is_prime(n)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_key_test() {
+        assert_eq!(natural_key(&42u32), 42);
+        assert_eq!(natural_key(&42u64), 42);
+        assert_eq!(natural_key(&-1i8), 0xff);
+        assert_eq!(natural_key(&'a'), 97);
+        assert_eq!(natural_key("ab"), natural_key(&String::from("ab")));
+        assert_ne!(natural_key("ab"), natural_key("ba"));
+        assert_ne!(natural_key(&(1, 2)), natural_key(&(2, 1)));
+    }
+
+    #[test]
+    fn hash_function_test() {
+        let mut rng = rand::rng();
+        let h = HashFunction::division(701);
+        assert_eq!(h.hash(1000), 299);
+        assert_eq!(h.slots(), 701);
+
+        for l in [0, 1, 5, 14, 63] {
+            let h = HashFunction::multiplication(l, &mut rng);
+            assert_eq!(h.slots(), 1 << l);
+            assert!((0..1000).all(|k| h.hash(k) < h.slots()));
+        }
+        // Example of section 11.3.2, where w = 32 and a = 2654435769 become w = 64 and a = 2654435769·2^32.
+        let h = HashFunction::Multiplication { a: 2654435769 << 32, l: 14 };
+        assert_eq!(h.hash(123456), 67);
+
+        let h = HashFunction::universal(1000, &mut rng);
+        assert!([0, 1, u64::MAX, HashFunction::P].iter().all(|&k| h.hash(k) < 1000));
+    }
+
+    #[test]
+    fn universal_collisions_test() {
+        // For distinct keys k and l, Pr{h(k) = h(l)} <= 1/m over the random choice of h.
+        let mut rng = rand::rng();
+        let m = 16;
+        let trials = 20000;
+        let collisions = (0..trials).filter(|_| {
+            let h = HashFunction::universal(m, &mut rng);
+            h.hash(3) == h.hash(3 + 16)
+        }).count();
+        assert!((collisions as f64) < 1.5 * trials as f64 / m as f64, "{collisions}");
+    }
+
+    #[test]
+    fn hash_family_test() {
+        let mut rng = rand::rng();
+        assert_eq!(HashFamily::Division.table_size(8), 11);
+        assert_eq!(HashFamily::Multiplication.table_size(9), 16);
+        assert_eq!(HashFamily::Universal.table_size(0), 1);
+        for family in [HashFamily::Division, HashFamily::Multiplication, HashFamily::Universal] {
+            let m = family.table_size(100);
+            assert_eq!(family.function(m, &mut rng).slots(), m);
+        }
+        assert_eq!([0, 1, 2, 14, 97, 98].map(next_prime), [2, 2, 2, 17, 97, 101]);
+    }
+
+    #[test]
+    #[should_panic(expected = "table size 12 is not a power of 2")]
+    fn multiplication_size_test() {
+        HashFamily::Multiplication.function(12, &mut rand::rng());
+    }
+}
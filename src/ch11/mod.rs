@@ -0,0 +1,11 @@
+mod hash_function;
+mod probe_stats;
+mod chained_hash_table;
+mod open_addressing;
+mod perfect_hashing;
+
+pub use hash_function::*;
+pub use probe_stats::*;
+pub use chained_hash_table::*;
+pub use open_addressing::*;
+pub use perfect_hashing::*;
\ No newline at end of file
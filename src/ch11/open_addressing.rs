@@ -0,0 +1,391 @@
+use std::cell::Cell;
+use std::hash::Hash;
+use std::mem;
+use rand::Rng;
+use crate::ch11::{natural_key, HashFamily, HashFunction, ProbeStats};
+
+// Probe sequence of an open-addressing table, where `h1` and `h2` are auxiliary hash functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Probing {
+    // h(k, i) = (h1(k) + i) mod m
+    Linear,
+    // h(k, i) = (h1(k) + i/2 + i^2/2) mod m, which visits every slot if m is a power of 2 (Problem 11-3),
+    // and at least half of them if m is prime
+    Quadratic,
+    // h(k, i) = (h1(k) + i·h2(k)) mod m, where h2(k) is odd if m is a power of 2, and in 1..m if m is prime.
+    // h2 is always drawn from the universal family, independently of h1, so that keys colliding under h1
+    // still get different probe sequences, even with the division method, which has a single function per size.
+    Double,
+}
+
+#[derive(Debug, Clone)]
+enum Slot<K, V> {
+    Empty,
+    Deleted,
+    Occupied(K, V),
+}
+
+// Hash table with open addressing (section 11.4), where deletion leaves a DELETED marker that searches probe past.
+// Whenever an insertion would take the fraction of occupied and deleted slots above `max_load_factor`,
+// the table is rebuilt with new hash functions, doubling its size unless most of those slots are deleted.
+#[derive(Debug, Clone)]
+pub struct OpenAddressingHashTable<K, V> {
+    table: Vec<Slot<K, V>>,
+    family: HashFamily,
+    probing: Probing,
+    h1: HashFunction,
+    h2: HashFunction,
+    len: usize,
+    deleted: usize,
+    max_load_factor: f64,
+    stats: Cell<ProbeStats>,
+}
+
+impl<K: Hash + Eq, V> OpenAddressingHashTable<K, V> {
+    pub fn new(family: HashFamily, probing: Probing) -> Self {
+        Self::with_slots(family, probing, 8)
+    }
+
+    // Create a table of at least `m` slots, rounded up to a size that suits `family`.
+    pub fn with_slots(family: HashFamily, probing: Probing, m: usize) -> Self {
+        let m = family.table_size(m);
+        let mut rng = rand::rng();
+        OpenAddressingHashTable {
+            table: (0..m).map(|_| Slot::Empty).collect(),
+            family,
+            probing,
+            h1: family.function(m, &mut rng),
+            h2: secondary(m, &mut rng),
+            len: 0,
+            deleted: 0,
+            max_load_factor: 0.5,
+            stats: Cell::new(ProbeStats::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn slots(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn probing(&self) -> Probing {
+        self.probing
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots() as f64
+    }
+
+    pub fn max_load_factor(&self) -> f64 {
+        self.max_load_factor
+    }
+
+    // Set the load factor above which the table is rebuilt, which must be less than 1,
+    // and at most 1/2 for quadratic probing in a table of prime size.
+    pub fn set_max_load_factor(&mut self, max_load_factor: f64) {
+        assert!(0.0 < max_load_factor && max_load_factor < 1.0, "max load factor must be in (0, 1)");
+        if self.probing == Probing::Quadratic && self.family == HashFamily::Division {
+            assert!(max_load_factor <= 0.5, "quadratic probing in a table of prime size needs a max load factor of at most 0.5");
+        }
+        self.max_load_factor = max_load_factor;
+    }
+
+    pub fn stats(&self) -> ProbeStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(ProbeStats::default());
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+
+    // HASH-SEARCH
+    pub fn get(&self, k: &K) -> Option<&V> {
+        match &self.table[self.search(k).0?] {
+            Slot::Occupied(_, v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        let j = self.search(k).0?;
+        match &mut self.table[j] {
+            Slot::Occupied(_, v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    // HASH-INSERT, which replaces and returns the value of `k` if `k` is already in the table.
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        let (found, mut available) = self.search(&k);
+        if let Some(j) = found
+            && let Slot::Occupied(_, old) = &mut self.table[j]
+        {
+            return Some(mem::replace(old, v));
+        }
+
+        // Only a new key can take the table over its load factor, so replacing a value never rebuilds it.
+        let m = self.slots();
+        if (self.len + self.deleted + 1) as f64 > self.max_load_factor * m as f64 {
+            // Double the table if it is at least half full of elements, and only clear the DELETED markers otherwise.
+            let m = if (self.len + 1) as f64 > self.max_load_factor * m as f64 / 2.0 { 2 * m } else { m };
+            self.rebuild(self.family.table_size(m));
+            available = self.first_empty(natural_key(&k));
+        }
+        // The load factor leaves an available slot in every probe sequence.
+        let j = available.expect("hash table overflow");
+        if let Slot::Deleted = self.table[j] {
+            self.deleted -= 1;
+        }
+        self.table[j] = Slot::Occupied(k, v);
+        self.len += 1;
+        None
+    }
+
+    // Delete `k` by marking its slot DELETED (Exercise 11.4-2).
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let j = self.search(k).0?;
+        self.len -= 1;
+        self.deleted += 1;
+        match mem::replace(&mut self.table[j], Slot::Deleted) {
+            Slot::Occupied(_, v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.table.iter().filter_map(|slot| match slot {
+            Slot::Occupied(k, v) => Some((k, v)),
+            _ => None,
+        })
+    }
+
+    // Return the slot of `k` if `k` is in the table, and the first slot of the probe sequence
+    // where `k` could be inserted, which is EMPTY or DELETED.
+    fn search(&self, k: &K) -> (Option<usize>, Option<usize>) {
+        let key = natural_key(k);
+        let m = self.slots();
+        let (mut found, mut available, mut probes) = (None, None, m);
+        for i in 0..m {
+            let j = self.probe(key, i);
+            match &self.table[j] {
+                Slot::Empty => {
+                    available.get_or_insert(j);
+                    probes = i + 1;
+                    break;
+                }
+                Slot::Deleted => {
+                    available.get_or_insert(j);
+                }
+                Slot::Occupied(key, _) if key == k => {
+                    found = Some(j);
+                    probes = i + 1;
+                    break;
+                }
+                Slot::Occupied(..) => {}
+            }
+        }
+        let mut stats = self.stats.get();
+        stats.record(probes as u64);
+        self.stats.set(stats);
+        (found, available)
+    }
+
+    // The `i`-th slot of the probe sequence of `key`.
+    fn probe(&self, key: u64, i: usize) -> usize {
+        let m = self.slots();
+        let h1 = self.h1.hash(key);
+        let offset = match self.probing {
+            Probing::Linear => i,
+            Probing::Quadratic => i * (i + 1) / 2 % m,
+            Probing::Double => {
+                let h2 = self.h2.hash(key);
+                let step = if m.is_power_of_two() { h2 | 1 } else { 1 + h2 };
+                i * step % m
+            }
+        };
+        (h1 + offset) % m
+    }
+
+    // The first EMPTY slot of the probe sequence of `key`, without counting the probes,
+    // which is where a new key goes in a table without DELETED slots.
+    fn first_empty(&self, key: u64) -> Option<usize> {
+        (0..self.slots()).map(|i| self.probe(key, i)).find(|&j| matches!(self.table[j], Slot::Empty))
+    }
+
+    fn rebuild(&mut self, m: usize) {
+        let mut rng = rand::rng();
+        self.h1 = self.family.function(m, &mut rng);
+        self.h2 = secondary(m, &mut rng);
+        let table = mem::replace(&mut self.table, (0..m).map(|_| Slot::Empty).collect());
+        self.len = 0;
+        self.deleted = 0;
+        // The new table has no DELETED slots and no duplicate keys, so each element goes into the first EMPTY slot.
+        for slot in table {
+            if let Slot::Occupied(k, v) = slot {
+                let j = self.first_empty(natural_key(&k)).unwrap();
+                self.table[j] = Slot::Occupied(k, v);
+                self.len += 1;
+            }
+        }
+        let mut stats = self.stats.get();
+        stats.resizes += 1;
+        self.stats.set(stats);
+    }
+}
+
+// Draw the auxiliary hash function h2 of double hashing for `m` slots, into 0..m if m is a power of 2,
+// which is made odd, and into 0..m-1 otherwise, which is shifted to 1..m.
+fn secondary<R: Rng>(m: usize, rng: &mut R) -> HashFunction {
+    HashFunction::universal(if m.is_power_of_two() { m } else { m - 1 }, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use rand::Rng;
+    use super::*;
+
+    const FAMILIES: [HashFamily; 3] = [HashFamily::Division, HashFamily::Multiplication, HashFamily::Universal];
+    const PROBINGS: [Probing; 3] = [Probing::Linear, Probing::Quadratic, Probing::Double];
+
+    #[test]
+    fn open_addressing_test() {
+        for family in FAMILIES {
+            for probing in PROBINGS {
+                let mut t = OpenAddressingHashTable::new(family, probing);
+                assert_eq!(t.probing(), probing);
+                assert_eq!(t.insert(String::from("apple"), 1), None);
+                assert_eq!(t.insert(String::from("banana"), 2), None);
+                assert_eq!(t.insert(String::from("apple"), 3), Some(1));
+                assert_eq!(t.get(&String::from("apple")), Some(&3));
+                *t.get_mut(&String::from("banana")).unwrap() += 10;
+                assert_eq!(t.remove(&String::from("banana")), Some(12));
+                assert_eq!(t.remove(&String::from("banana")), None);
+                assert!(!t.contains_key(&String::from("banana")));
+                assert_eq!(t.iter().collect::<Vec<_>>(), [(&String::from("apple"), &3)]);
+                assert_eq!(t.len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn open_addressing_random_test() {
+        let mut rng = rand::rng();
+        for family in FAMILIES {
+            for probing in PROBINGS {
+                let mut t = OpenAddressingHashTable::new(family, probing);
+                let mut model = HashMap::new();
+                for i in 0..3000 {
+                    let k: u64 = rng.random_range(0..500);
+                    match rng.random_range(0..3) {
+                        0 | 1 => assert_eq!(t.insert(k, i), model.insert(k, i), "{family:?}, {probing:?}"),
+                        _ => assert_eq!(t.remove(&k), model.remove(&k), "{family:?}, {probing:?}"),
+                    }
+                    assert_eq!(t.len(), model.len());
+                    assert!(t.load_factor() <= t.max_load_factor());
+                }
+                assert!(model.iter().all(|(k, v)| t.get(k) == Some(v)));
+            }
+        }
+    }
+
+    #[test]
+    fn probe_sequence_test() {
+        // Every probe sequence of a table whose size is a power of 2 is a permutation of the slots.
+        for probing in PROBINGS {
+            let t = OpenAddressingHashTable::<u64, ()>::with_slots(HashFamily::Multiplication, probing, 64);
+            for key in 0..100 {
+                let slots: HashSet<usize> = (0..64).map(|i| t.probe(key, i)).collect();
+                assert_eq!(slots.len(), 64, "{probing:?}");
+            }
+        }
+        // With a prime size, linear probing and double hashing still visit every slot, and quadratic probing half of them.
+        for (probing, visited) in [(Probing::Linear, 67), (Probing::Quadratic, 34), (Probing::Double, 67)] {
+            let t = OpenAddressingHashTable::<u64, ()>::with_slots(HashFamily::Division, probing, 67);
+            for key in 0..100 {
+                let slots: HashSet<usize> = (0..67).map(|i| t.probe(key, i)).collect();
+                assert_eq!(slots.len(), visited, "{probing:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn double_hashing_test() {
+        // Keys congruent modulo m all collide under the division method, but double hashing steps through
+        // the table by a different amount for most of them, unlike linear probing.
+        let m = 1031;
+        let keys: Vec<u64> = (0..200).map(|i| i * m + 5).collect();
+        let t = OpenAddressingHashTable::<u64, ()>::with_slots(HashFamily::Division, Probing::Double, m as usize);
+        assert!(keys.iter().all(|&k| t.probe(k, 0) == 5));
+        let second: HashSet<usize> = keys.iter().map(|&k| t.probe(k, 1)).collect();
+        assert!(second.len() > 100, "{}", second.len());
+
+        let average_probes = |probing| {
+            let mut t = OpenAddressingHashTable::with_slots(HashFamily::Division, probing, m as usize);
+            for &k in &keys {
+                t.insert(k, ());
+            }
+            t.reset_stats();
+            assert!(keys.iter().all(|k| t.contains_key(k)));
+            t.stats().average_probes()
+        };
+        assert!(average_probes(Probing::Linear) > 100.0);
+        assert!(average_probes(Probing::Double) < 5.0);
+    }
+
+    #[test]
+    fn open_addressing_replace_test() {
+        // Replacing the value of a key never rebuilds the table, even when a new key would.
+        let mut t = OpenAddressingHashTable::with_slots(HashFamily::Division, Probing::Linear, 11);
+        for k in 0..5u64 {
+            t.insert(k, k);
+        }
+        assert_eq!(t.insert(0, 10), Some(0));
+        assert_eq!(t.stats().resizes, 0);
+        t.insert(5, 5);
+        assert_eq!(t.stats().resizes, 1);
+    }
+
+    #[test]
+    fn open_addressing_stats_test() {
+        let mut t = OpenAddressingHashTable::with_slots(HashFamily::Division, Probing::Linear, 11);
+        t.set_max_load_factor(0.9);
+        // Keys congruent modulo 11 form one cluster.
+        for k in 0..5u64 {
+            t.insert(11 * k, k);
+        }
+        t.reset_stats();
+        assert_eq!(t.get(&44), Some(&4));
+        assert_eq!(t.get(&55), None);
+        assert_eq!(t.stats(), ProbeStats { operations: 2, probes: 11, max_probes: 6, resizes: 0 });
+
+        // Deleted slots are probed past, and reused by insertions.
+        t.remove(&0);
+        assert_eq!(t.get(&44), Some(&4));
+        t.insert(0, 0);
+        assert_eq!(t.deleted, 0);
+
+        for k in 5..10u64 {
+            t.insert(11 * k, k);
+        }
+        assert!(t.stats().resizes > 0);
+        assert!(t.slots() > 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "quadratic probing in a table of prime size needs a max load factor of at most 0.5")]
+    fn open_addressing_max_load_factor_test() {
+        OpenAddressingHashTable::<u64, u64>::new(HashFamily::Division, Probing::Quadratic).set_max_load_factor(0.75);
+    }
+}
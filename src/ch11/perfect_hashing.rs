@@ -0,0 +1,232 @@
+use std::hash::Hash;
+use std::mem;
+use crate::ch11::{natural_key, HashFunction};
+
+/*
+    Perfect Hashing (section 11.5)
+    A static set of n keys is stored in two levels of hash tables. The primary table hashes the keys into n slots
+    with a universal hash function h, and the n_j keys hashing to slot j are stored in a secondary table of
+    m_j = n_j^2 slots with a universal hash function h_j chosen to have no collisions.
+    A search probes exactly one slot of one secondary table, so it takes O(1) worst-case time.
+
+    A random h_j has no collisions with probability at least 1/2 (Theorem 11.9), and E[Σ m_j] < 2n (Corollary 11.11),
+    so by Markov's inequality Σ m_j <= c·n with probability at least 1 - 2/c. The builder retries both levels
+    until they succeed, which takes O(1) expected attempts each, and O(n) expected time in total.
+*/
+#[derive(Debug, Clone)]
+pub struct PerfectHashBuilder {
+    space_factor: f64,
+}
+
+// What it took to build a `PerfectHashTable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildReport {
+    // Number of primary hash functions drawn until Σ m_j met the space bound
+    pub primary_attempts: usize,
+    // Number of hash functions drawn for each secondary table until it had no collisions, or 0 if it is empty
+    pub secondary_attempts: Vec<usize>,
+    // The size m_j of each secondary table
+    pub secondary_sizes: Vec<usize>,
+}
+
+impl BuildReport {
+    // Total number of secondary slots Σ m_j.
+    pub fn total_slots(&self) -> usize {
+        self.secondary_sizes.iter().sum()
+    }
+}
+
+impl PerfectHashBuilder {
+    pub fn new() -> Self {
+        PerfectHashBuilder { space_factor: 4.0 }
+    }
+
+    // Set `c` of the space bound Σ m_j <= c·n, where `c > 2` for each primary attempt to succeed with positive probability.
+    // The default is 4, for which each attempt succeeds with probability at least 1/2.
+    pub fn space_factor(mut self, c: f64) -> Self {
+        assert!(c > 2.0, "space factor must be greater than 2");
+        self.space_factor = c;
+        self
+    }
+
+    pub fn build<K: Hash + Eq, V>(&self, entries: Vec<(K, V)>) -> PerfectHashTable<K, V> {
+        let mut rng = rand::rng();
+        let n = entries.len();
+        let m = n.max(1);
+        let keys: Vec<u64> = entries.iter().map(|(k, _)| natural_key(k) % HashFunction::P).collect();
+
+        let bound = self.space_factor * n as f64;
+        let mut primary_attempts = 0;
+        let (h, counts) = loop {
+            primary_attempts += 1;
+            let h = HashFunction::universal(m, &mut rng);
+            let mut counts = vec![0usize; m];
+            for &key in &keys {
+                counts[h.hash(key)] += 1;
+            }
+            if counts.iter().map(|n_j| n_j * n_j).sum::<usize>() as f64 <= bound {
+                break (h, counts);
+            }
+        };
+
+        let mut buckets: Vec<Vec<(u64, K, V)>> = counts.iter().map(|&n_j| Vec::with_capacity(n_j)).collect();
+        for (key, (k, v)) in keys.into_iter().zip(entries) {
+            buckets[h.hash(key)].push((key, k, v));
+        }
+
+        let mut secondary_attempts = Vec::with_capacity(m);
+        let mut secondary = Vec::with_capacity(m);
+        for bucket in buckets {
+            check_distinct(&bucket);
+            let m_j = bucket.len() * bucket.len();
+            let mut attempts = 0;
+            let h_j = if m_j == 0 { None } else {
+                loop {
+                    attempts += 1;
+                    let h_j = HashFunction::universal(m_j, &mut rng);
+                    let mut occupied = vec![false; m_j];
+                    if bucket.iter().all(|&(key, ..)| !mem::replace(&mut occupied[h_j.hash(key)], true)) {
+                        break Some(h_j);
+                    }
+                }
+            };
+            let mut slots: Vec<Option<(K, V)>> = (0..m_j).map(|_| None).collect();
+            for (key, k, v) in bucket {
+                slots[h_j.unwrap().hash(key)] = Some((k, v));
+            }
+            secondary_attempts.push(attempts);
+            secondary.push(Secondary { h: h_j, slots });
+        }
+
+        let secondary_sizes = secondary.iter().map(|s| s.slots.len()).collect();
+        let report = BuildReport { primary_attempts, secondary_attempts, secondary_sizes };
+        PerfectHashTable { h, secondary, len: n, report }
+    }
+}
+
+impl Default for PerfectHashBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Keys with the same natural key modulo p collide under every hash function of the universal family,
+// so they could never be separated by a secondary table.
+fn check_distinct<K: Eq, V>(bucket: &[(u64, K, V)]) {
+    for (i, (key, k, _)) in bucket.iter().enumerate() {
+        for (other_key, other_k, _) in &bucket[..i] {
+            if key == other_key {
+                assert!(k != other_k, "duplicate key");
+                panic!("distinct keys with the same natural key cannot be hashed perfectly");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Secondary<K, V> {
+    h: Option<HashFunction>, // `None` for an empty table
+    slots: Vec<Option<(K, V)>>,
+}
+
+// Static hash table built by a `PerfectHashBuilder`, which finds any key in one probe.
+#[derive(Debug, Clone)]
+pub struct PerfectHashTable<K, V> {
+    h: HashFunction,
+    secondary: Vec<Secondary<K, V>>,
+    len: usize,
+    report: BuildReport,
+}
+
+impl<K: Hash + Eq, V> PerfectHashTable<K, V> {
+    // Build a table with the default space bound Σ m_j <= 4n.
+    pub fn new(entries: Vec<(K, V)>) -> Self {
+        PerfectHashBuilder::new().build(entries)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn report(&self) -> &BuildReport {
+        &self.report
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        let key = natural_key(k) % HashFunction::P;
+        let s = &self.secondary[self.h.hash(key)];
+        match &s.slots[s.h?.hash(key)] {
+            Some((other, v)) if other == k => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.secondary.iter().flat_map(|s| s.slots.iter().flatten()).map(|(k, v)| (k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn perfect_hash_table_test() {
+        let mut rng = rand::rng();
+        for n in [0, 1, 2, 10, 1000] {
+            let keys: HashSet<u64> = (0..n).map(|_| rng.random_range(0..1_000_000)).collect();
+            let entries: Vec<(u64, u64)> = keys.iter().map(|&k| (k, 2 * k)).collect();
+            let t = PerfectHashTable::new(entries);
+            assert_eq!(t.len(), keys.len());
+            assert!(keys.iter().all(|k| t.get(k) == Some(&(2 * k))));
+            assert!((0..1000).filter(|k| !keys.contains(k)).all(|k| !t.contains_key(&k)));
+            assert_eq!(t.iter().count(), keys.len());
+
+            let report = t.report();
+            assert!(report.total_slots() <= 4 * keys.len());
+            assert!(report.primary_attempts >= 1);
+            assert_eq!(report.secondary_sizes.len(), keys.len().max(1));
+            for (&m_j, &attempts) in report.secondary_sizes.iter().zip(&report.secondary_attempts) {
+                assert_eq!(m_j == 0, attempts == 0);
+                assert_eq!(m_j.isqrt().pow(2), m_j);
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_hash_builder_test() {
+        // Figure 11.6
+        let keys = [10, 22, 37, 40, 52, 60, 70, 72, 75];
+        let t = PerfectHashBuilder::new().space_factor(2.5).build(keys.map(|k| (k, ())).to_vec());
+        assert!(keys.iter().all(|k| t.contains_key(k)));
+        assert!(t.report().total_slots() as f64 <= 2.5 * 9.0);
+
+        let words = ["apple", "banana", "cherry", "date", "elderberry", "fig", "grape"];
+        let t = PerfectHashBuilder::default().build(words.iter().map(|w| (w.to_string(), w.len())).collect());
+        assert_eq!(t.get(&String::from("cherry")), Some(&6));
+        assert_eq!(t.get(&String::from("kiwi")), None);
+        assert!(!PerfectHashTable::<u32, ()>::new(Vec::new()).contains_key(&0));
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate key")]
+    fn perfect_hash_duplicate_test() {
+        PerfectHashTable::new(vec![(1, 'a'), (2, 'b'), (1, 'c')]);
+    }
+
+    #[test]
+    #[should_panic(expected = "space factor must be greater than 2")]
+    fn perfect_hash_space_factor_test() {
+        PerfectHashBuilder::new().space_factor(2.0);
+    }
+}
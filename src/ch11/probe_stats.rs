@@ -0,0 +1,22 @@
+// Statistics of the operations on a hash table, where a probe examines one slot of an open-addressing table
+// or one element of a chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProbeStats {
+    pub operations: u64,
+    pub probes: u64,
+    pub max_probes: u64,
+    pub resizes: u64,
+}
+
+impl ProbeStats {
+    pub fn average_probes(&self) -> f64 {
+        if self.operations == 0 { return 0.0; }
+        self.probes as f64 / self.operations as f64
+    }
+
+    pub(super) fn record(&mut self, probes: u64) {
+        self.operations += 1;
+        self.probes += probes;
+        self.max_probes = self.max_probes.max(probes);
+    }
+}
@@ -0,0 +1,576 @@
+use std::cmp::Ordering;
+use std::mem;
+
+#[derive(Debug, Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    p: usize,
+    left: usize,
+    right: usize,
+}
+
+// Binary search tree (chapter 12), whose nodes are stored in a vector and linked by index, with `NIL` for a missing node.
+// A deleted node is replaced by the last node of the vector, so the nodes always occupy `nodes[..len]`.
+// The tree is not balanced, so its operations run in O(h) time, where h is the height of the tree.
+#[derive(Debug, Clone)]
+pub struct BinarySearchTree<K, V> {
+    nodes: Vec<Node<K, V>>,
+    root: usize,
+}
+
+const NIL: usize = usize::MAX;
+
+impl<K: Ord, V> BinarySearchTree<K, V> {
+    pub fn new() -> Self {
+        BinarySearchTree { nodes: Vec::new(), root: NIL }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Number of edges on the longest simple downward path from the root to a leaf, or `None` for an empty tree.
+    pub fn height(&self) -> Option<usize> {
+        self.root().map(|x| x.height())
+    }
+
+    pub fn root(&self) -> Option<NodeRef<'_, K, V>> {
+        self.node_ref(self.root)
+    }
+
+    // ITERATIVE-TREE-SEARCH
+    pub fn search(&self, k: &K) -> Option<NodeRef<'_, K, V>> {
+        self.node_ref(self.search_index(k))
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.search_index(k) != NIL
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.search(k).map(|x| x.value())
+    }
+
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        let x = self.search_index(k);
+        if x == NIL { return None; }
+        Some(&mut self.nodes[x].value)
+    }
+
+    pub fn minimum(&self) -> Option<NodeRef<'_, K, V>> {
+        self.root().map(|x| x.minimum())
+    }
+
+    pub fn maximum(&self) -> Option<NodeRef<'_, K, V>> {
+        self.root().map(|x| x.maximum())
+    }
+
+    // The node following the node with key `k` in an inorder tree walk, or `None` if `k` is absent or the maximum.
+    pub fn successor(&self, k: &K) -> Option<NodeRef<'_, K, V>> {
+        self.search(k)?.successor()
+    }
+
+    pub fn predecessor(&self, k: &K) -> Option<NodeRef<'_, K, V>> {
+        self.search(k)?.predecessor()
+    }
+
+    // TREE-INSERT, which replaces the value and returns the old one if `key` is already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut y = NIL;
+        let mut x = self.root;
+        let mut order = Ordering::Equal;
+        while x != NIL {
+            y = x;
+            order = key.cmp(&self.nodes[x].key);
+            x = match order {
+                Ordering::Less => self.nodes[x].left,
+                Ordering::Greater => self.nodes[x].right,
+                Ordering::Equal => return Some(mem::replace(&mut self.nodes[x].value, value)),
+            };
+        }
+
+        let z = self.nodes.len();
+        self.nodes.push(Node { key, value, p: y, left: NIL, right: NIL });
+        if y == NIL {
+            self.root = z; // The tree was empty.
+        } else if order == Ordering::Less {
+            self.nodes[y].left = z;
+        } else {
+            self.nodes[y].right = z;
+        }
+        None
+    }
+
+    // TREE-DELETE, returning `None` if `k` is absent.
+    pub fn delete(&mut self, k: &K) -> Option<V> {
+        let z = self.search_index(k);
+        if z == NIL { return None; }
+        let Node { left, right, .. } = self.nodes[z];
+        if left == NIL {
+            self.transplant(z, right);
+        } else if right == NIL {
+            self.transplant(z, left);
+        } else {
+            let y = self.minimum_index(right);
+            if y != right {
+                let y_right = self.nodes[y].right;
+                self.transplant(y, y_right);
+                self.nodes[y].right = right;
+                self.nodes[right].p = y;
+            }
+            self.transplant(z, y);
+            self.nodes[y].left = left;
+            self.nodes[left].p = y;
+        }
+        Some(self.remove_node(z).value)
+    }
+
+    // Call `f` on each node in sorted order, by recursion as in INORDER-TREE-WALK.
+    pub fn inorder_tree_walk<F: FnMut(&K, &V)>(&self, mut f: F) {
+        self.walk_aux(self.root, Order::Inorder, &mut f);
+    }
+
+    // Call `f` on the root before the nodes in its subtrees.
+    pub fn preorder_tree_walk<F: FnMut(&K, &V)>(&self, mut f: F) {
+        self.walk_aux(self.root, Order::Preorder, &mut f);
+    }
+
+    // Call `f` on the root after the nodes in its subtrees.
+    pub fn postorder_tree_walk<F: FnMut(&K, &V)>(&self, mut f: F) {
+        self.walk_aux(self.root, Order::Postorder, &mut f);
+    }
+
+    // Iterate in sorted order without recursion or a stack (Exercise 12.1-3).
+    pub fn inorder(&self) -> Walk<'_, K, V> {
+        Walk::new(self, Order::Inorder)
+    }
+
+    pub fn preorder(&self) -> Walk<'_, K, V> {
+        Walk::new(self, Order::Preorder)
+    }
+
+    pub fn postorder(&self) -> Walk<'_, K, V> {
+        Walk::new(self, Order::Postorder)
+    }
+
+    pub fn iter(&self) -> Walk<'_, K, V> {
+        self.inorder()
+    }
+
+    fn node_ref(&self, x: usize) -> Option<NodeRef<'_, K, V>> {
+        if x == NIL { return None; }
+        Some(NodeRef { tree: self, x })
+    }
+
+    fn search_index(&self, k: &K) -> usize {
+        let mut x = self.root;
+        while x != NIL {
+            x = match k.cmp(&self.nodes[x].key) {
+                Ordering::Less => self.nodes[x].left,
+                Ordering::Greater => self.nodes[x].right,
+                Ordering::Equal => return x,
+            };
+        }
+        NIL
+    }
+
+    fn minimum_index(&self, mut x: usize) -> usize {
+        while self.nodes[x].left != NIL {
+            x = self.nodes[x].left;
+        }
+        x
+    }
+
+    // TRANSPLANT, which replaces the subtree rooted at `u` with the subtree rooted at `v`.
+    fn transplant(&mut self, u: usize, v: usize) {
+        let p = self.nodes[u].p;
+        if p == NIL {
+            self.root = v;
+        } else if u == self.nodes[p].left {
+            self.nodes[p].left = v;
+        } else {
+            self.nodes[p].right = v;
+        }
+        if v != NIL {
+            self.nodes[v].p = p;
+        }
+    }
+
+    // Remove the unlinked node `z` from the vector, relinking the last node, which moves into its place.
+    fn remove_node(&mut self, z: usize) -> Node<K, V> {
+        let last = self.nodes.len() - 1;
+        let node = self.nodes.swap_remove(z);
+        if z != last {
+            let Node { p, left, right, .. } = self.nodes[z];
+            if p == NIL {
+                self.root = z;
+            } else if self.nodes[p].left == last {
+                self.nodes[p].left = z;
+            } else {
+                self.nodes[p].right = z;
+            }
+            for child in [left, right] {
+                if child != NIL {
+                    self.nodes[child].p = z;
+                }
+            }
+        }
+        node
+    }
+
+    fn walk_aux<F: FnMut(&K, &V)>(&self, x: usize, order: Order, f: &mut F) {
+        if x == NIL { return; }
+        let node = &self.nodes[x];
+        if order == Order::Preorder { f(&node.key, &node.value); }
+        self.walk_aux(node.left, order, f);
+        if order == Order::Inorder { f(&node.key, &node.value); }
+        self.walk_aux(node.right, order, f);
+        if order == Order::Postorder { f(&node.key, &node.value); }
+    }
+}
+
+impl<K: Ord, V> Default for BinarySearchTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a BinarySearchTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Walk<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Read-only view of a node, for navigating the structure of a `BinarySearchTree`.
+pub struct NodeRef<'a, K, V> {
+    tree: &'a BinarySearchTree<K, V>,
+    x: usize,
+}
+
+impl<K, V> Clone for NodeRef<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for NodeRef<'_, K, V> {}
+
+impl<'a, K, V> NodeRef<'a, K, V> {
+    pub fn key(self) -> &'a K {
+        &self.node().key
+    }
+
+    pub fn value(self) -> &'a V {
+        &self.node().value
+    }
+
+    pub fn parent(self) -> Option<Self> {
+        self.to(self.node().p)
+    }
+
+    pub fn left(self) -> Option<Self> {
+        self.to(self.node().left)
+    }
+
+    pub fn right(self) -> Option<Self> {
+        self.to(self.node().right)
+    }
+
+    // TREE-MINIMUM of the subtree rooted at this node
+    pub fn minimum(self) -> Self {
+        let mut x = self;
+        while let Some(left) = x.left() {
+            x = left;
+        }
+        x
+    }
+
+    // TREE-MAXIMUM of the subtree rooted at this node
+    pub fn maximum(self) -> Self {
+        let mut x = self;
+        while let Some(right) = x.right() {
+            x = right;
+        }
+        x
+    }
+
+    // TREE-SUCCESSOR, which is the minimum of the right subtree if there is one,
+    // and otherwise the lowest ancestor whose left child is also an ancestor.
+    pub fn successor(self) -> Option<Self> {
+        if let Some(right) = self.right() {
+            return Some(right.minimum());
+        }
+        let mut x = self;
+        let mut y = x.parent();
+        while let Some(p) = y && x.x == p.node().right {
+            x = p;
+            y = p.parent();
+        }
+        y
+    }
+
+    pub fn predecessor(self) -> Option<Self> {
+        if let Some(left) = self.left() {
+            return Some(left.maximum());
+        }
+        let mut x = self;
+        let mut y = x.parent();
+        while let Some(p) = y && x.x == p.node().left {
+            x = p;
+            y = p.parent();
+        }
+        y
+    }
+
+    // Height of the subtree rooted at this node
+    pub fn height(self) -> usize {
+        let left = self.left().map_or(0, |x| x.height() + 1);
+        let right = self.right().map_or(0, |x| x.height() + 1);
+        left.max(right)
+    }
+
+    fn node(&self) -> &'a Node<K, V> {
+        &self.tree.nodes[self.x]
+    }
+
+    fn to(self, x: usize) -> Option<Self> {
+        if x == NIL { return None; }
+        Some(NodeRef { tree: self.tree, x })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Preorder,
+    Inorder,
+    Postorder,
+}
+
+// Where a walk has arrived at the current node from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Down, // From the parent, so no subtree has been visited yet.
+    FromLeft,
+    FromRight,
+}
+
+// Iterator over the nodes of a `BinarySearchTree` in preorder, inorder or postorder, which uses O(1) space.
+// Instead of a stack, it follows parent pointers upwards and tests which child it came from (Exercise 12.1-3).
+#[derive(Debug, Clone)]
+pub struct Walk<'a, K, V> {
+    tree: &'a BinarySearchTree<K, V>,
+    order: Order,
+    x: usize,
+    step: Step,
+    len: usize,
+}
+
+impl<'a, K, V> Walk<'a, K, V> {
+    fn new(tree: &'a BinarySearchTree<K, V>, order: Order) -> Self {
+        Walk { tree, order, x: tree.root, step: Step::Down, len: tree.nodes.len() }
+    }
+}
+
+impl<'a, K, V> Iterator for Walk<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nodes = &self.tree.nodes;
+        while self.x != NIL {
+            let x = self.x;
+            let node = &nodes[x];
+            let visit = match self.step {
+                Step::Down => {
+                    if node.left != NIL {
+                        self.x = node.left;
+                    } else {
+                        self.step = Step::FromLeft;
+                    }
+                    self.order == Order::Preorder
+                }
+                Step::FromLeft => {
+                    if node.right != NIL {
+                        self.x = node.right;
+                        self.step = Step::Down;
+                    } else {
+                        self.step = Step::FromRight;
+                    }
+                    self.order == Order::Inorder
+                }
+                Step::FromRight => {
+                    self.x = node.p;
+                    if node.p != NIL && nodes[node.p].left == x {
+                        self.step = Step::FromLeft;
+                    }
+                    self.order == Order::Postorder
+                }
+            };
+            if visit {
+                self.len -= 1;
+                return Some((&node.key, &node.value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Walk<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use rand::Rng;
+    use super::*;
+
+    // Check the binary-search-tree property and that every child points back to its parent.
+    fn check<K: Ord, V>(t: &BinarySearchTree<K, V>) {
+        if t.root != NIL {
+            assert_eq!(t.nodes[t.root].p, NIL);
+        }
+        for (x, node) in t.nodes.iter().enumerate() {
+            if node.left != NIL {
+                assert_eq!(t.nodes[node.left].p, x);
+                assert!(t.nodes[node.left].key < node.key);
+            }
+            if node.right != NIL {
+                assert_eq!(t.nodes[node.right].p, x);
+                assert!(t.nodes[node.right].key > node.key);
+            }
+        }
+        assert_eq!(t.inorder().count(), t.len());
+        assert!(t.inorder().map(|(k, _)| k).is_sorted_by(|a, b| a < b));
+    }
+
+    fn keys<'a, I: Iterator<Item = (&'a i32, &'a ())>>(walk: I) -> Vec<i32> {
+        walk.map(|(&k, _)| k).collect()
+    }
+
+    fn recursive_keys<F: Fn(&BinarySearchTree<i32, ()>, &mut dyn FnMut(&i32, &()))>(t: &BinarySearchTree<i32, ()>, walk: F) -> Vec<i32> {
+        let mut keys = Vec::new();
+        walk(t, &mut |&k, _| keys.push(k));
+        keys
+    }
+
+    // Figure 12.2
+    fn figure_12_2() -> BinarySearchTree<i32, ()> {
+        let mut t = BinarySearchTree::new();
+        for k in [15, 6, 18, 3, 7, 17, 20, 2, 4, 13, 9] {
+            t.insert(k, ());
+        }
+        t
+    }
+
+    #[test]
+    fn binary_search_tree_test() {
+        let t = figure_12_2();
+        check(&t);
+        assert_eq!(t.len(), 11);
+        assert_eq!(t.height(), Some(4));
+        assert_eq!(t.root().map(|x| *x.key()), Some(15));
+        assert_eq!(t.minimum().map(|x| *x.key()), Some(2));
+        assert_eq!(t.maximum().map(|x| *x.key()), Some(20));
+        assert_eq!(t.successor(&15).map(|x| *x.key()), Some(17));
+        assert_eq!(t.successor(&13).map(|x| *x.key()), Some(15));
+        assert_eq!(t.successor(&20).map(|x| *x.key()), None);
+        assert_eq!(t.predecessor(&17).map(|x| *x.key()), Some(15));
+        assert_eq!(t.predecessor(&9).map(|x| *x.key()), Some(7));
+        assert_eq!(t.predecessor(&2).map(|x| *x.key()), None);
+        assert!(t.search(&13).is_some() && t.search(&8).is_none());
+
+        let x = t.search(&13).unwrap();
+        assert_eq!(*x.parent().unwrap().key(), 7);
+        assert_eq!(x.left().map(|x| *x.key()), Some(9));
+        assert!(x.right().is_none());
+        assert_eq!(x.height(), 1);
+
+        let empty = BinarySearchTree::<i32, ()>::default();
+        assert!(empty.is_empty() && empty.root().is_none() && empty.minimum().is_none());
+        assert_eq!(empty.height(), None);
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn binary_search_tree_walk_test() {
+        let t = figure_12_2();
+        let inorder = [2, 3, 4, 6, 7, 9, 13, 15, 17, 18, 20];
+        let preorder = [15, 6, 3, 2, 4, 7, 13, 9, 18, 17, 20];
+        let postorder = [2, 4, 3, 9, 13, 7, 6, 17, 20, 18, 15];
+        assert_eq!(keys(t.inorder()), inorder);
+        assert_eq!(keys(t.preorder()), preorder);
+        assert_eq!(keys(t.postorder()), postorder);
+        assert_eq!(keys((&t).into_iter()), inorder);
+        assert_eq!(recursive_keys(&t, |t, f| t.inorder_tree_walk(f)), inorder);
+        assert_eq!(recursive_keys(&t, |t, f| t.preorder_tree_walk(f)), preorder);
+        assert_eq!(recursive_keys(&t, |t, f| t.postorder_tree_walk(f)), postorder);
+        assert_eq!(t.postorder().len(), 11);
+    }
+
+    #[test]
+    fn binary_search_tree_map_test() {
+        let mut t = BinarySearchTree::new();
+        assert_eq!(t.insert("b", 1), None);
+        assert_eq!(t.insert("a", 2), None);
+        assert_eq!(t.insert("b", 3), Some(1));
+        *t.get_mut(&"a").unwrap() += 10;
+        assert_eq!(t.get(&"a"), Some(&12));
+        assert_eq!(t.get(&"b"), Some(&3));
+        assert!(t.contains_key(&"a") && !t.contains_key(&"c"));
+        assert_eq!(t.len(), 2);
+    }
+
+    #[test]
+    fn binary_search_tree_delete_test() {
+        // Each case of Figure 12.4
+        let mut t = figure_12_2();
+        assert_eq!(t.delete(&4), Some(())); // No children
+        assert_eq!(t.delete(&13), Some(())); // Only a left child
+        assert_eq!(t.delete(&6), Some(())); // Two children, where the successor is the right child
+        assert_eq!(t.delete(&15), Some(())); // Two children, where the successor is deeper in the right subtree
+        assert_eq!(t.delete(&15), None);
+        check(&t);
+        assert_eq!(t.root().map(|x| *x.key()), Some(17));
+        assert_eq!(keys(t.inorder()), [2, 3, 7, 9, 17, 18, 20]);
+        assert_eq!(keys(t.preorder()), [17, 7, 3, 2, 9, 18, 20]);
+        for k in [17, 2, 20, 3, 9, 18, 7] {
+            assert_eq!(t.delete(&k), Some(()));
+            check(&t);
+        }
+        assert!(t.is_empty() && t.root().is_none());
+    }
+
+    // Apply random operations, checking the tree and comparing against a model after each of them.
+    #[test]
+    fn binary_search_tree_random_test() {
+        let mut rng = rand::rng();
+        let mut t = BinarySearchTree::new();
+        let mut model = BTreeMap::new();
+        for i in 0..2000 {
+            let k = rng.random_range(0..200);
+            if rng.random_bool(0.6) {
+                assert_eq!(t.insert(k, i), model.insert(k, i));
+            } else {
+                assert_eq!(t.delete(&k), model.remove(&k));
+            }
+            check(&t);
+            assert!(t.iter().eq(model.iter()));
+            assert_eq!(t.successor(&k).map(|x| x.key()), model.range(k + 1..).next().filter(|_| model.contains_key(&k)).map(|(k, _)| k));
+        }
+        let mut postorder: Vec<_> = t.postorder().collect();
+        let mut preorder: Vec<_> = t.preorder().collect();
+        postorder.sort();
+        preorder.sort();
+        assert!(postorder.into_iter().eq(model.iter()) && preorder.into_iter().eq(model.iter()));
+    }
+}
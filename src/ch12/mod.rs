@@ -0,0 +1,3 @@
+mod binary_search_tree;
+
+pub use binary_search_tree::*;
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod red_black_tree;
+mod persistent_set;
+
+pub use red_black_tree::*;
+pub use persistent_set::*;
\ No newline at end of file
@@ -0,0 +1,345 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Node<K> {
+    key: K,
+    left: Link<K>,
+    right: Link<K>,
+    height: usize, // Number of nodes on the longest downward path from this node to a leaf
+}
+
+type Link<K> = Option<Arc<Node<K>>>;
+
+// Persistent ordered set (Problem 13-1), in which `insert` and `delete` leave the set unchanged and return a new version.
+// Nodes are never modified, so an update copies only the nodes on the path from the root to the changed node,
+// along with the nodes it rotates, and the new version shares all other nodes with the old one.
+// Nodes have no parent pointers, which would force every node to be copied.
+// The tree is kept balanced as an AVL tree (Problem 13-3), which is simpler than a red-black tree to rebalance
+// without parent pointers, so each update takes O(lg n) time and allocates O(lg n) new nodes.
+// The nodes are shared through `Arc`, so versions can be sent to other threads.
+#[derive(Debug)]
+pub struct PersistentSet<K> {
+    root: Link<K>,
+    len: usize,
+}
+
+impl<K: Ord + Clone> PersistentSet<K> {
+    pub fn new() -> Self {
+        PersistentSet { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Number of edges on the longest simple downward path from the root to a leaf, or `None` for an empty set.
+    pub fn height(&self) -> Option<usize> {
+        self.root.as_ref().map(|x| x.height - 1)
+    }
+
+    pub fn contains(&self, k: &K) -> bool {
+        let mut x = &self.root;
+        while let Some(node) = x {
+            x = match k.cmp(&node.key) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    pub fn minimum(&self) -> Option<&K> {
+        let mut x = self.root.as_ref()?;
+        while let Some(left) = &x.left {
+            x = left;
+        }
+        Some(&x.key)
+    }
+
+    pub fn maximum(&self) -> Option<&K> {
+        let mut x = self.root.as_ref()?;
+        while let Some(right) = &x.right {
+            x = right;
+        }
+        Some(&x.key)
+    }
+
+    // PERSISTENT-TREE-INSERT, which returns a version that also contains `k`.
+    // If `k` is already present, the new version shares every node with this one.
+    pub fn insert(&self, k: K) -> Self {
+        match insert_aux(&self.root, k) {
+            Some(root) => PersistentSet { root, len: self.len + 1 },
+            None => self.clone(),
+        }
+    }
+
+    // Return a version that does not contain `k`.
+    // If `k` is absent, the new version shares every node with this one.
+    pub fn delete(&self, k: &K) -> Self {
+        match delete_aux(&self.root, k) {
+            Some(root) => PersistentSet { root, len: self.len - 1 },
+            None => self.clone(),
+        }
+    }
+
+    // Iterate in sorted order.
+    pub fn iter(&self) -> SetIter<'_, K> {
+        let mut iter = SetIter { stack: Vec::new(), len: self.len };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+// Cloning a version copies only the pointer to its root.
+impl<K> Clone for PersistentSet<K> {
+    fn clone(&self) -> Self {
+        PersistentSet { root: self.root.clone(), len: self.len }
+    }
+}
+
+impl<K: Ord + Clone> Default for PersistentSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone> FromIterator<K> for PersistentSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        iter.into_iter().fold(PersistentSet::new(), |s, k| s.insert(k))
+    }
+}
+
+impl<'a, K: Ord + Clone> IntoIterator for &'a PersistentSet<K> {
+    type Item = &'a K;
+    type IntoIter = SetIter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+fn height<K>(x: &Link<K>) -> usize {
+    x.as_ref().map_or(0, |x| x.height)
+}
+
+fn new_node<K>(key: K, left: Link<K>, right: Link<K>) -> Arc<Node<K>> {
+    let height = height(&left).max(height(&right)) + 1;
+    Arc::new(Node { key, left, right, height })
+}
+
+// Make a node from `key` and subtrees whose heights differ by at most 2, rotating if they differ by 2
+// so that the heights of the subtrees of the new node differ by at most 1. Only the rotated nodes are copied.
+fn balance<K: Clone>(key: K, left: Link<K>, right: Link<K>) -> Arc<Node<K>> {
+    let (hl, hr) = (height(&left), height(&right));
+    if hl > hr + 1 {
+        let l = left.unwrap();
+        if height(&l.left) >= height(&l.right) {
+            // Single right rotation
+            new_node(l.key.clone(), l.left.clone(), Some(new_node(key, l.right.clone(), right)))
+        } else {
+            // Double rotation, which lifts the right child of `l` to the top
+            let lr = l.right.as_ref().unwrap();
+            let a = new_node(l.key.clone(), l.left.clone(), lr.left.clone());
+            let b = new_node(key, lr.right.clone(), right);
+            new_node(lr.key.clone(), Some(a), Some(b))
+        }
+    } else if hr > hl + 1 {
+        let r = right.unwrap();
+        if height(&r.right) >= height(&r.left) {
+            new_node(r.key.clone(), Some(new_node(key, left, r.left.clone())), r.right.clone())
+        } else {
+            let rl = r.left.as_ref().unwrap();
+            let a = new_node(key, left, rl.left.clone());
+            let b = new_node(r.key.clone(), rl.right.clone(), r.right.clone());
+            new_node(rl.key.clone(), Some(a), Some(b))
+        }
+    } else {
+        new_node(key, left, right)
+    }
+}
+
+// Insert `k` into the subtree rooted at `x`, returning the new subtree, or `None` if `k` is already present.
+fn insert_aux<K: Ord + Clone>(x: &Link<K>, k: K) -> Option<Link<K>> {
+    let Some(node) = x else {
+        return Some(Some(new_node(k, None, None)));
+    };
+    let node = match k.cmp(&node.key) {
+        Ordering::Less => balance(node.key.clone(), insert_aux(&node.left, k)?, node.right.clone()),
+        Ordering::Greater => balance(node.key.clone(), node.left.clone(), insert_aux(&node.right, k)?),
+        Ordering::Equal => return None,
+    };
+    Some(Some(node))
+}
+
+// Delete `k` from the subtree rooted at `x`, returning the new subtree, or `None` if `k` is absent.
+fn delete_aux<K: Ord + Clone>(x: &Link<K>, k: &K) -> Option<Link<K>> {
+    let node = x.as_ref()?;
+    let node = match k.cmp(&node.key) {
+        Ordering::Less => balance(node.key.clone(), delete_aux(&node.left, k)?, node.right.clone()),
+        Ordering::Greater => balance(node.key.clone(), node.left.clone(), delete_aux(&node.right, k)?),
+        Ordering::Equal => match (&node.left, &node.right) {
+            (None, right) => return Some(right.clone()),
+            (left, None) => return Some(left.clone()),
+            (left, Some(right)) => {
+                // Replace the key with its successor, which is removed from the right subtree.
+                let (successor, right) = delete_minimum(right);
+                balance(successor, left.clone(), right)
+            }
+        },
+    };
+    Some(Some(node))
+}
+
+// Delete the minimum of the subtree rooted at `x`, returning the minimum and the new subtree.
+fn delete_minimum<K: Clone>(x: &Arc<Node<K>>) -> (K, Link<K>) {
+    match &x.left {
+        None => (x.key.clone(), x.right.clone()),
+        Some(left) => {
+            let (minimum, left) = delete_minimum(left);
+            (minimum, Some(balance(x.key.clone(), left, x.right.clone())))
+        }
+    }
+}
+
+// Iterator over the keys of a `PersistentSet` in sorted order, which keeps the path to the next key on a stack,
+// since nodes have no parent pointers.
+#[derive(Debug, Clone)]
+pub struct SetIter<'a, K> {
+    stack: Vec<&'a Node<K>>,
+    len: usize,
+}
+
+impl<'a, K> SetIter<'a, K> {
+    fn push_left(&mut self, mut x: &'a Link<K>) {
+        while let Some(node) = x {
+            self.stack.push(node);
+            x = &node.left;
+        }
+    }
+}
+
+impl<'a, K> Iterator for SetIter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+        self.len -= 1;
+        Some(&node.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K> ExactSizeIterator for SetIter<'_, K> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+    use rand::Rng;
+    use super::*;
+
+    // Check the binary-search-tree property, the heights and the AVL balance of every node,
+    // returning the number of nodes.
+    fn check<K: Ord>(x: &Link<K>, lo: Option<&K>, hi: Option<&K>) -> usize {
+        let Some(node) = x else { return 0; };
+        assert!(lo.is_none_or(|lo| *lo < node.key) && hi.is_none_or(|hi| node.key < *hi));
+        assert_eq!(node.height, height(&node.left).max(height(&node.right)) + 1);
+        assert!(height(&node.left).abs_diff(height(&node.right)) <= 1);
+        check(&node.left, lo, Some(&node.key)) + check(&node.right, Some(&node.key), hi) + 1
+    }
+
+    fn validate<K: Ord + Clone>(s: &PersistentSet<K>) {
+        assert_eq!(check(&s.root, None, None), s.len());
+    }
+
+    fn nodes<K>(x: &Link<K>, set: &mut HashSet<*const Node<K>>) {
+        if let Some(node) = x {
+            set.insert(Arc::as_ptr(node));
+            nodes(&node.left, set);
+            nodes(&node.right, set);
+        }
+    }
+
+    // Number of nodes of `new` that are not shared with `old`
+    fn new_nodes<K>(old: &PersistentSet<K>, new: &PersistentSet<K>) -> usize {
+        let (mut old_nodes, mut new_nodes) = (HashSet::new(), HashSet::new());
+        nodes(&old.root, &mut old_nodes);
+        nodes(&new.root, &mut new_nodes);
+        new_nodes.difference(&old_nodes).count()
+    }
+
+    #[test]
+    fn persistent_set_test() {
+        // Figure 13.8
+        let s: PersistentSet<i32> = [4, 3, 8, 2, 7, 10].into_iter().collect();
+        let t = s.insert(5);
+        validate(&s);
+        validate(&t);
+        assert!(s.iter().copied().eq([2, 3, 4, 7, 8, 10]));
+        assert!(t.iter().copied().eq([2, 3, 4, 5, 7, 8, 10]));
+        assert!(!s.contains(&5) && t.contains(&5));
+        assert_eq!((s.len(), t.len()), (6, 7));
+
+        // Only the path 4, 8, 7 to the new node is copied, along with the new node itself.
+        assert_eq!(new_nodes(&s, &t), 4);
+        let root = t.root.as_ref().unwrap();
+        assert!(Arc::ptr_eq(root.left.as_ref().unwrap(), s.root.as_ref().unwrap().left.as_ref().unwrap()));
+
+        let u = t.delete(&4);
+        validate(&u);
+        assert!(u.iter().copied().eq([2, 3, 5, 7, 8, 10]));
+        assert!(t.iter().copied().eq([2, 3, 4, 5, 7, 8, 10]));
+        assert_eq!((t.minimum(), t.maximum()), (Some(&2), Some(&10)));
+
+        // Updates that change nothing share the whole tree.
+        assert_eq!(new_nodes(&t, &t.insert(5)), 0);
+        assert_eq!(new_nodes(&t, &t.delete(&6)), 0);
+        assert_eq!(t.delete(&6).len(), 7);
+
+        let empty = PersistentSet::<i32>::default();
+        assert!(empty.is_empty() && empty.height().is_none() && empty.minimum().is_none());
+        assert_eq!(empty.delete(&1).len(), 0);
+        assert!((&empty).into_iter().next().is_none());
+    }
+
+    // Keep every version, checking after each update that all earlier versions are unchanged
+    // and that the update allocated O(lg n) new nodes.
+    #[test]
+    fn persistent_set_random_test() {
+        let mut rng = rand::rng();
+        let mut versions = vec![PersistentSet::new()];
+        let mut models = vec![BTreeSet::new()];
+        for _ in 0..1000 {
+            let k = rng.random_range(0..500);
+            let (s, mut model) = (versions.last().unwrap(), models.last().unwrap().clone());
+            let t = if rng.random_bool(0.7) {
+                model.insert(k);
+                s.insert(k)
+            } else {
+                model.remove(&k);
+                s.delete(&k)
+            };
+            validate(&t);
+            assert!(t.iter().eq(model.iter()));
+            assert_eq!(t.iter().len(), model.len());
+            let h = s.height().max(t.height()).map_or(0, |h| h + 1);
+            assert!(new_nodes(s, &t) <= 3 * h, "{} new nodes for height {h}", new_nodes(s, &t));
+            versions.push(t);
+            models.push(model);
+        }
+        for (s, model) in versions.iter().zip(&models) {
+            assert!(s.iter().eq(model.iter()));
+        }
+        let n = versions.last().unwrap().len() as f64;
+        assert!(versions.last().unwrap().height().unwrap() as f64 <= 1.45 * (n + 2.0).log2());
+    }
+}
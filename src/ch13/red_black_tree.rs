@@ -0,0 +1,725 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Black,
+}
+
+// Information kept in each node of an augmented red-black tree (chapter 17).
+// It must depend only on the key of the node and the information in its children, so that insertion, deletion and
+// rotations can maintain it by recomputing it at the O(lg n) nodes whose subtrees change (Theorem 17.1).
+pub trait Augment<K> {
+    type Data: Clone + PartialEq + Debug;
+
+    // The information in the sentinel, standing for an empty subtree
+    fn nil() -> Self::Data;
+
+    fn compute(key: &K, left: &Self::Data, right: &Self::Data) -> Self::Data;
+}
+
+// No augmentation, which is the default for `RedBlackTree`.
+impl<K> Augment<K> for () {
+    type Data = ();
+
+    fn nil() {}
+
+    fn compute(_: &K, _: &(), _: &()) {}
+}
+
+#[derive(Debug, Clone)]
+struct Node<K, V, D> {
+    entry: Option<(K, V)>, // `None` for the sentinel
+    data: D,
+    color: Color,
+    p: usize,
+    left: usize,
+    right: usize,
+}
+
+// Red-black tree (chapter 13), whose nodes are stored in a vector and linked by index.
+// `nodes[NIL]` is the black sentinel `T.nil`, which stands for every leaf and the parent of the root.
+// A deleted node is replaced by the last node of the vector, so the nodes always occupy `nodes[1..=len]`.
+// The height is at most 2 lg(n + 1) (Lemma 13.1), so every operation runs in O(lg n) time.
+// Each node also keeps the information of `A` about its subtree, which is nothing by default.
+#[derive(Debug, Clone)]
+pub struct RedBlackTree<K, V, A: Augment<K> = ()> {
+    nodes: Vec<Node<K, V, A::Data>>,
+    root: usize,
+    augment: PhantomData<A>,
+}
+
+pub(crate) const NIL: usize = 0;
+
+impl<K: Ord, V> RedBlackTree<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K: Ord, V, A: Augment<K>> RedBlackTree<K, V, A> {
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    // Number of black nodes on any simple path from the root down to a leaf, not counting the root itself.
+    pub fn black_height(&self) -> usize {
+        let mut x = self.root;
+        let mut bh = 0;
+        while x != NIL {
+            x = self.nodes[x].left;
+            if self.nodes[x].color == Color::Black {
+                bh += 1;
+            }
+        }
+        bh
+    }
+
+    // Number of edges on the longest simple downward path from the root to a leaf, or `None` for an empty tree.
+    pub fn height(&self) -> Option<usize> {
+        if self.root == NIL { return None; }
+        Some(self.height_aux(self.root))
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.search(k) != NIL
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.nodes[self.search(k)].entry.as_ref().map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        let x = self.search(k);
+        self.nodes[x].entry.as_mut().map(|(_, v)| v)
+    }
+
+    pub fn minimum(&self) -> Option<(&K, &V)> {
+        if self.root == NIL { return None; }
+        self.entry(minimum_index(&self.nodes, self.root))
+    }
+
+    pub fn maximum(&self) -> Option<(&K, &V)> {
+        if self.root == NIL { return None; }
+        self.entry(maximum_index(&self.nodes, self.root))
+    }
+
+    // The entry with the smallest key greater than `k`, whether `k` is present or not.
+    pub fn successor(&self, k: &K) -> Option<(&K, &V)> {
+        self.entry(self.lower_bound(k, false))
+    }
+
+    // The entry with the largest key less than `k`, whether `k` is present or not.
+    pub fn predecessor(&self, k: &K) -> Option<(&K, &V)> {
+        self.entry(self.upper_bound(k, false))
+    }
+
+    // RB-INSERT, which replaces the value and returns the old one if `key` is already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut y = NIL;
+        let mut x = self.root;
+        let mut order = Ordering::Equal;
+        while x != NIL {
+            y = x;
+            order = key.cmp(self.key(x));
+            x = match order {
+                Ordering::Less => self.nodes[x].left,
+                Ordering::Greater => self.nodes[x].right,
+                Ordering::Equal => return Some(mem::replace(&mut self.nodes[x].entry.as_mut().unwrap().1, value)),
+            };
+        }
+
+        let z = self.nodes.len();
+        self.nodes.push(Node { entry: Some((key, value)), data: A::nil(), color: Color::Red, p: y, left: NIL, right: NIL });
+        if y == NIL {
+            self.root = z;
+        } else if order == Ordering::Less {
+            self.nodes[y].left = z;
+        } else {
+            self.nodes[y].right = z;
+        }
+        self.update_path(z);
+        self.insert_fixup(z);
+        None
+    }
+
+    // RB-DELETE, returning `None` if `k` is absent.
+    pub fn delete(&mut self, k: &K) -> Option<V> {
+        let z = self.search(k);
+        if z == NIL { return None; }
+        let Node { left, right, .. } = self.nodes[z];
+        let mut y_original_color = self.nodes[z].color;
+        let x;
+        if left == NIL {
+            x = right;
+            self.transplant(z, right);
+        } else if right == NIL {
+            x = left;
+            self.transplant(z, left);
+        } else {
+            let y = minimum_index(&self.nodes, right);
+            y_original_color = self.nodes[y].color;
+            x = self.nodes[y].right;
+            if y != right {
+                self.transplant(y, x);
+                self.nodes[y].right = right;
+                self.nodes[right].p = y;
+            } else {
+                self.nodes[x].p = y; // In case `x` is the sentinel
+            }
+            self.transplant(z, y);
+            self.nodes[y].left = left;
+            self.nodes[left].p = y;
+            self.nodes[y].color = self.nodes[z].color;
+        }
+        // The subtrees that changed are those of the ancestors of `x`, and `x.p` is set even if `x` is the sentinel.
+        self.update_path(self.nodes[x].p);
+        if y_original_color == Color::Black {
+            self.delete_fixup(x);
+        }
+        Some(self.remove_node(z).1)
+    }
+
+    // Iterate in sorted order.
+    pub fn iter(&self) -> Iter<'_, K, V, A::Data> {
+        if self.root == NIL { return self.iter_between(NIL, NIL); }
+        self.iter_between(minimum_index(&self.nodes, self.root), maximum_index(&self.nodes, self.root))
+    }
+
+    // Iterate in sorted order over the keys in `range`, which takes O(lg n + m) time to report m entries.
+    // This takes `RangeBounds` rather than `IntoRange`, since the half-open `Range` that `IntoRange` produces
+    // cannot end after the largest value of `K`, so an inclusive range up to it would overflow.
+    // Looking up each bound directly also works for keys without a successor, such as strings.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Iter<'_, K, V, A::Data> {
+        let front = match range.start_bound() {
+            Bound::Included(k) => self.lower_bound(k, true),
+            Bound::Excluded(k) => self.lower_bound(k, false),
+            Bound::Unbounded => minimum_index(&self.nodes, self.root),
+        };
+        let back = match range.end_bound() {
+            Bound::Included(k) => self.upper_bound(k, true),
+            Bound::Excluded(k) => self.upper_bound(k, false),
+            Bound::Unbounded => maximum_index(&self.nodes, self.root),
+        };
+        if front == NIL || back == NIL || self.key(front) > self.key(back) {
+            return self.iter_between(NIL, NIL);
+        }
+        self.iter_between(front, back)
+    }
+
+    // Check every red-black property along with the binary-search-tree property, the parent pointers
+    // and the augmented information.
+    // Panics with a description of the first violation found.
+    pub fn validate(&self) {
+        let nil = &self.nodes[NIL];
+        assert!(nil.entry.is_none() && nil.color == Color::Black, "sentinel must be black");
+        assert!(self.nodes[self.root].color == Color::Black, "root must be black");
+        if self.root != NIL {
+            assert!(self.nodes[self.root].p == NIL, "root must not have a parent");
+        }
+        let (n, _) = self.validate_aux(self.root, None, None);
+        assert!(n == self.len(), "{} nodes are reachable from the root, but the tree has {}", n, self.len());
+    }
+
+    pub(crate) fn root_index(&self) -> usize {
+        self.root
+    }
+
+    pub(crate) fn left(&self, x: usize) -> usize {
+        self.nodes[x].left
+    }
+
+    pub(crate) fn right(&self, x: usize) -> usize {
+        self.nodes[x].right
+    }
+
+    pub(crate) fn data(&self, x: usize) -> &A::Data {
+        &self.nodes[x].data
+    }
+
+    pub(crate) fn entry(&self, x: usize) -> Option<(&K, &V)> {
+        self.nodes[x].entry.as_ref().map(|(k, v)| (k, v))
+    }
+
+    pub(crate) fn key(&self, x: usize) -> &K {
+        &self.nodes[x].entry.as_ref().unwrap().0
+    }
+
+    fn iter_between(&self, front: usize, back: usize) -> Iter<'_, K, V, A::Data> {
+        Iter { nodes: &self.nodes, front, back }
+    }
+
+    pub(crate) fn search(&self, k: &K) -> usize {
+        let mut x = self.root;
+        while x != NIL {
+            x = match k.cmp(self.key(x)) {
+                Ordering::Less => self.nodes[x].left,
+                Ordering::Greater => self.nodes[x].right,
+                Ordering::Equal => return x,
+            };
+        }
+        NIL
+    }
+
+    // The node with the smallest key greater than `k`, or greater than or equal to `k` if `inclusive`.
+    fn lower_bound(&self, k: &K, inclusive: bool) -> usize {
+        let mut x = self.root;
+        let mut y = NIL;
+        while x != NIL {
+            let order = self.key(x).cmp(k);
+            if order == Ordering::Greater || inclusive && order == Ordering::Equal {
+                y = x;
+                x = self.nodes[x].left;
+            } else {
+                x = self.nodes[x].right;
+            }
+        }
+        y
+    }
+
+    // The node with the largest key less than `k`, or less than or equal to `k` if `inclusive`.
+    fn upper_bound(&self, k: &K, inclusive: bool) -> usize {
+        let mut x = self.root;
+        let mut y = NIL;
+        while x != NIL {
+            let order = self.key(x).cmp(k);
+            if order == Ordering::Less || inclusive && order == Ordering::Equal {
+                y = x;
+                x = self.nodes[x].right;
+            } else {
+                x = self.nodes[x].left;
+            }
+        }
+        y
+    }
+
+    fn height_aux(&self, x: usize) -> usize {
+        let Node { left, right, .. } = self.nodes[x];
+        let left = if left == NIL { 0 } else { self.height_aux(left) + 1 };
+        let right = if right == NIL { 0 } else { self.height_aux(right) + 1 };
+        left.max(right)
+    }
+
+    // LEFT-ROTATE, which turns the right child `y` of `x` into the parent of `x`.
+    fn left_rotate(&mut self, x: usize) {
+        let y = self.nodes[x].right;
+        let beta = self.nodes[y].left;
+        self.nodes[x].right = beta;
+        if beta != NIL {
+            self.nodes[beta].p = x;
+        }
+        self.transplant(x, y);
+        self.nodes[y].left = x;
+        self.nodes[x].p = y;
+        self.update(x);
+        self.update(y);
+    }
+
+    // RIGHT-ROTATE, the inverse of `left_rotate`
+    fn right_rotate(&mut self, x: usize) {
+        let y = self.nodes[x].left;
+        let beta = self.nodes[y].right;
+        self.nodes[x].left = beta;
+        if beta != NIL {
+            self.nodes[beta].p = x;
+        }
+        self.transplant(x, y);
+        self.nodes[y].right = x;
+        self.nodes[x].p = y;
+        self.update(x);
+        self.update(y);
+    }
+
+    // Recompute the augmented information of `x` from its children.
+    fn update(&mut self, x: usize) {
+        let Node { left, right, .. } = self.nodes[x];
+        let data = A::compute(self.key(x), &self.nodes[left].data, &self.nodes[right].data);
+        self.nodes[x].data = data;
+    }
+
+    // Recompute the augmented information of `x` and its ancestors.
+    fn update_path(&mut self, mut x: usize) {
+        while x != NIL {
+            self.update(x);
+            x = self.nodes[x].p;
+        }
+    }
+
+    fn rotate(&mut self, x: usize, left: bool) {
+        if left { self.left_rotate(x) } else { self.right_rotate(x) }
+    }
+
+    // The left child of `x` if `left`, and otherwise the right child
+    fn child(&self, x: usize, left: bool) -> usize {
+        if left { self.nodes[x].left } else { self.nodes[x].right }
+    }
+
+    // RB-TRANSPLANT, which sets the parent of `v` even if it is the sentinel, as `delete_fixup` relies on.
+    fn transplant(&mut self, u: usize, v: usize) {
+        let p = self.nodes[u].p;
+        if p == NIL {
+            self.root = v;
+        } else if u == self.nodes[p].left {
+            self.nodes[p].left = v;
+        } else {
+            self.nodes[p].right = v;
+        }
+        self.nodes[v].p = p;
+    }
+
+    // RB-INSERT-FIXUP, where the cases for `z.p` being a right child mirror those for a left child.
+    fn insert_fixup(&mut self, mut z: usize) {
+        while self.nodes[self.nodes[z].p].color == Color::Red {
+            let p = self.nodes[z].p;
+            let g = self.nodes[p].p;
+            let left = p == self.nodes[g].left;
+            let y = self.child(g, !left); // The uncle of `z`
+            if self.nodes[y].color == Color::Red {
+                // Case 1
+                self.nodes[p].color = Color::Black;
+                self.nodes[y].color = Color::Black;
+                self.nodes[g].color = Color::Red;
+                z = g;
+            } else {
+                if z == self.child(p, !left) {
+                    // Case 2
+                    z = p;
+                    self.rotate(z, left);
+                }
+                // Case 3
+                let p = self.nodes[z].p;
+                self.nodes[p].color = Color::Black;
+                self.nodes[g].color = Color::Red;
+                self.rotate(g, !left);
+            }
+        }
+        let root = self.root;
+        self.nodes[root].color = Color::Black;
+    }
+
+    // RB-DELETE-FIXUP, where `x` carries an extra black until it is red, the root, or the extra black is absorbed.
+    fn delete_fixup(&mut self, mut x: usize) {
+        while x != self.root && self.nodes[x].color == Color::Black {
+            let p = self.nodes[x].p;
+            let left = x == self.nodes[p].left;
+            let mut w = self.child(p, !left); // The sibling of `x`
+            if self.nodes[w].color == Color::Red {
+                // Case 1
+                self.nodes[w].color = Color::Black;
+                self.nodes[p].color = Color::Red;
+                self.rotate(p, left);
+                w = self.child(p, !left);
+            }
+            let near = self.child(w, left);
+            let far = self.child(w, !left);
+            if self.nodes[near].color == Color::Black && self.nodes[far].color == Color::Black {
+                // Case 2
+                self.nodes[w].color = Color::Red;
+                x = p;
+            } else {
+                if self.nodes[far].color == Color::Black {
+                    // Case 3
+                    self.nodes[near].color = Color::Black;
+                    self.nodes[w].color = Color::Red;
+                    self.rotate(w, !left);
+                    w = self.child(p, !left);
+                }
+                // Case 4
+                self.nodes[w].color = self.nodes[p].color;
+                self.nodes[p].color = Color::Black;
+                let far = self.child(w, !left);
+                self.nodes[far].color = Color::Black;
+                self.rotate(p, left);
+                x = self.root;
+            }
+        }
+        self.nodes[x].color = Color::Black;
+    }
+
+    // Remove the unlinked node `z` from the vector, relinking the last node, which moves into its place.
+    fn remove_node(&mut self, z: usize) -> (K, V) {
+        let last = self.nodes.len() - 1;
+        let node = self.nodes.swap_remove(z);
+        if z != last {
+            let Node { p, left, right, .. } = self.nodes[z];
+            if p == NIL {
+                self.root = z;
+            } else if self.nodes[p].left == last {
+                self.nodes[p].left = z;
+            } else {
+                self.nodes[p].right = z;
+            }
+            for child in [left, right] {
+                if child != NIL {
+                    self.nodes[child].p = z;
+                }
+            }
+        }
+        self.nodes[NIL].p = NIL;
+        node.entry.unwrap()
+    }
+
+    // Validate the subtree rooted at `x`, whose keys must lie strictly between `lo` and `hi`,
+    // returning its number of nodes and its black-height counting `x`.
+    fn validate_aux(&self, x: usize, lo: Option<&K>, hi: Option<&K>) -> (usize, usize) {
+        if x == NIL { return (0, 1); }
+        let Node { color, left, right, .. } = self.nodes[x];
+        let k = self.key(x);
+        assert!(lo.is_none_or(|lo| lo < k) && hi.is_none_or(|hi| k < hi), "node {x} is out of order");
+        for child in [left, right] {
+            if child == NIL { continue; }
+            assert!(self.nodes[child].p == x, "node {child} does not point back to its parent {x}");
+            assert!(color == Color::Black || self.nodes[child].color == Color::Black, "red node {x} has a red child {child}");
+        }
+        let (n_left, bh_left) = self.validate_aux(left, lo, Some(k));
+        let (n_right, bh_right) = self.validate_aux(right, Some(k), hi);
+        assert!(bh_left == bh_right, "subtrees of node {x} have black-heights {bh_left} and {bh_right}");
+        let data = A::compute(k, &self.nodes[left].data, &self.nodes[right].data);
+        assert!(self.nodes[x].data == data, "node {x} has augmented information {:?} instead of {data:?}", self.nodes[x].data);
+        (n_left + n_right + 1, bh_left + (color == Color::Black) as usize)
+    }
+}
+
+impl<K: Ord, V, A: Augment<K>> Default for RedBlackTree<K, V, A> {
+    fn default() -> Self {
+        let nil = Node { entry: None, data: A::nil(), color: Color::Black, p: NIL, left: NIL, right: NIL };
+        RedBlackTree { nodes: vec![nil], root: NIL, augment: PhantomData }
+    }
+}
+
+impl<'a, K: Ord, V, A: Augment<K>> IntoIterator for &'a RedBlackTree<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, A::Data>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+fn minimum_index<K, V, D>(nodes: &[Node<K, V, D>], mut x: usize) -> usize {
+    while nodes[x].left != NIL {
+        x = nodes[x].left;
+    }
+    x
+}
+
+fn maximum_index<K, V, D>(nodes: &[Node<K, V, D>], mut x: usize) -> usize {
+    while nodes[x].right != NIL {
+        x = nodes[x].right;
+    }
+    x
+}
+
+fn successor_index<K, V, D>(nodes: &[Node<K, V, D>], mut x: usize) -> usize {
+    if nodes[x].right != NIL {
+        return minimum_index(nodes, nodes[x].right);
+    }
+    let mut y = nodes[x].p;
+    while y != NIL && x == nodes[y].right {
+        x = y;
+        y = nodes[y].p;
+    }
+    y
+}
+
+fn predecessor_index<K, V, D>(nodes: &[Node<K, V, D>], mut x: usize) -> usize {
+    if nodes[x].left != NIL {
+        return maximum_index(nodes, nodes[x].left);
+    }
+    let mut y = nodes[x].p;
+    while y != NIL && x == nodes[y].left {
+        x = y;
+        y = nodes[y].p;
+    }
+    y
+}
+
+// Iterator over the entries of a `RedBlackTree` from `front` to `back` in sorted order, from either end,
+// where `D` is the augmented information of the tree.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V, D = ()> {
+    nodes: &'a [Node<K, V, D>],
+    front: usize,
+    back: usize, // `NIL` with `front` once the iterator is exhausted
+}
+
+impl<'a, K, V, D> Iterator for Iter<'a, K, V, D> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.front;
+        if x == NIL { return None; }
+        if x == self.back {
+            self.front = NIL;
+            self.back = NIL;
+        } else {
+            self.front = successor_index(self.nodes, x);
+        }
+        self.nodes[x].entry.as_ref().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V, D> DoubleEndedIterator for Iter<'_, K, V, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let x = self.back;
+        if x == NIL { return None; }
+        if x == self.front {
+            self.front = NIL;
+            self.back = NIL;
+        } else {
+            self.back = predecessor_index(self.nodes, x);
+        }
+        self.nodes[x].entry.as_ref().map(|(k, v)| (k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use rand::Rng;
+    use rand::seq::SliceRandom;
+    use super::*;
+
+    #[test]
+    fn red_black_tree_test() {
+        let mut t = RedBlackTree::new();
+        assert!(t.is_empty());
+        assert_eq!((t.height(), t.black_height()), (None, 0));
+        t.validate();
+
+        // Inserting sorted keys would make an unbalanced binary search tree a path.
+        for k in 0..1000 {
+            assert_eq!(t.insert(k, k * k), None);
+            t.validate();
+        }
+        assert_eq!(t.len(), 1000);
+        assert!(t.height().unwrap() <= 2 * 10);
+        assert_eq!(t.get(&30), Some(&900));
+        assert_eq!(t.insert(30, 0), Some(900));
+        *t.get_mut(&30).unwrap() += 1;
+        assert_eq!(t.get(&30), Some(&1));
+        assert!(t.contains_key(&999) && !t.contains_key(&1000));
+        assert_eq!(t.minimum(), Some((&0, &0)));
+        assert_eq!(t.maximum(), Some((&999, &998001)));
+        assert!(t.iter().map(|(&k, _)| k).eq(0..1000));
+        assert!(t.iter().rev().map(|(&k, _)| k).eq((0..1000).rev()));
+
+        for k in (0..1000).step_by(2) {
+            let expected = if k == 30 { 1 } else { k * k };
+            assert_eq!(t.delete(&k), Some(expected));
+            t.validate();
+        }
+        assert_eq!(t.delete(&0), None);
+        assert_eq!(t.successor(&500).map(|(&k, _)| k), Some(501));
+        assert_eq!(t.successor(&501).map(|(&k, _)| k), Some(503));
+        assert_eq!(t.predecessor(&501).map(|(&k, _)| k), Some(499));
+        assert_eq!(t.predecessor(&1).map(|(&k, _)| k), None);
+        assert!((&t).into_iter().map(|(&k, _)| k).eq((1..1000).step_by(2)));
+    }
+
+    #[test]
+    fn red_black_tree_range_test() {
+        let mut t = RedBlackTree::new();
+        for k in (0..100).step_by(10) {
+            t.insert(k, ());
+        }
+        let keys = |it: Iter<'_, i32, ()>| it.map(|(&k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys(t.range(20..50)), [20, 30, 40]);
+        assert_eq!(keys(t.range(15..=50)), [20, 30, 40, 50]);
+        assert_eq!(keys(t.range(..30)), [0, 10, 20]);
+        assert_eq!(keys(t.range(75..)), [80, 90]);
+        assert_eq!(keys(t.range(..)), keys(t.iter()));
+        assert_eq!(keys(t.range(21..29)), []);
+        assert_eq!(keys(t.range(-50..-10)), []);
+        assert_eq!(keys(t.range(200..)), []);
+        assert_eq!(t.range(30..=60).rev().map(|(&k, _)| k).collect::<Vec<_>>(), [60, 50, 40, 30]);
+        assert_eq!(RedBlackTree::<usize, ()>::new().range(..).next(), None);
+
+        // No bound is moved past the maximum key, so a key equal to `K::MAX` is allowed.
+        let mut t = RedBlackTree::new();
+        for k in [1, 200, u8::MAX] {
+            t.insert(k, ());
+        }
+        let keys = |it: Iter<'_, u8, ()>| it.map(|(&k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys(t.range(0..10)), [1]);
+        assert_eq!(keys(t.range(..)), [1, 200, 255]);
+        assert_eq!(keys(t.range(200..=u8::MAX)), [200, 255]);
+        assert_eq!(keys(t.range((Bound::Excluded(200), Bound::Unbounded))), [255]);
+        assert_eq!(keys(t.range((Bound::Excluded(1), Bound::Excluded(u8::MAX)))), [200]);
+
+        let mut t = RedBlackTree::new();
+        for k in ["apple", "banana", "cherry"] {
+            t.insert(k, ());
+        }
+        assert_eq!(t.range("b"..="cherry").map(|(&k, _)| k).collect::<Vec<_>>(), ["banana", "cherry"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "red node")]
+    fn red_black_tree_validate_red_test() {
+        let mut t = RedBlackTree::new();
+        for k in 0..3 {
+            t.insert(k, ());
+        }
+        // Give the red left child of the root a red child.
+        let x = t.nodes[t.root].left;
+        let y = t.nodes.len();
+        t.nodes.push(Node { entry: Some((-1, ())), data: (), color: Color::Red, p: x, left: NIL, right: NIL });
+        t.nodes[x].left = y;
+        t.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "black-heights")]
+    fn red_black_tree_validate_black_height_test() {
+        let mut t = RedBlackTree::new();
+        for k in 0..3 {
+            t.insert(k, ());
+        }
+        // Make the red left child of the root black.
+        let x = t.nodes[t.root].left;
+        t.nodes[x].color = Color::Black;
+        t.validate();
+    }
+
+    // Apply random operations, validating the tree and comparing against a model after each of them.
+    #[test]
+    fn red_black_tree_random_test() {
+        let mut rng = rand::rng();
+        for _ in 0..10 {
+            let mut t = RedBlackTree::new();
+            let mut model = BTreeMap::new();
+            for i in 0..1000 {
+                let k: i32 = rng.random_range(0..300);
+                if rng.random_bool(0.6) {
+                    assert_eq!(t.insert(k, i), model.insert(k, i));
+                } else {
+                    assert_eq!(t.delete(&k), model.remove(&k));
+                }
+                t.validate();
+                assert_eq!(t.len(), model.len());
+                assert_eq!(t.successor(&k), model.range(k + 1..).next());
+                assert_eq!(t.predecessor(&k), model.range(..k).next_back());
+                let (a, b) = (rng.random_range(0..300), rng.random_range(0..300));
+                assert!(t.range(a.min(b)..=a.max(b)).eq(model.range(a.min(b)..=a.max(b))));
+            }
+            assert!(t.iter().eq(model.iter()));
+
+            let mut keys: Vec<_> = model.keys().copied().collect();
+            keys.shuffle(&mut rng);
+            for k in keys {
+                assert_eq!(t.delete(&k), model.remove(&k));
+                t.validate();
+            }
+            assert!(t.is_empty());
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use crate::ch13::{Augment, RedBlackTree, NIL};
+
+// Closed interval [low, high], ordered by its low endpoint and then by its high endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub low: T,
+    pub high: T,
+}
+
+impl<T: Ord> Interval<T> {
+    pub fn new(low: T, high: T) -> Self {
+        assert!(low <= high, "low endpoint must not exceed high endpoint");
+        Interval { low, high }
+    }
+
+    // Whether the intervals have a point in common, by the interval trichotomy.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.low <= other.high && other.low <= self.high
+    }
+}
+
+impl<T: Ord> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (low, high) = range.into_inner();
+        Interval::new(low, high)
+    }
+}
+
+// The maximum high endpoint in a subtree, or `None` for an empty subtree.
+#[derive(Debug, Clone)]
+struct MaxHigh;
+
+impl<T: Ord + Clone + Debug> Augment<(Interval<T>, u64)> for MaxHigh {
+    type Data = Option<T>;
+
+    fn nil() -> Option<T> {
+        None
+    }
+
+    fn compute((key, _): &(Interval<T>, u64), left: &Option<T>, right: &Option<T>) -> Option<T> {
+        let max = left.as_ref().max(right.as_ref()).filter(|&m| *m > key.high);
+        Some(max.unwrap_or(&key.high).clone())
+    }
+}
+
+// Interval tree (section 17.3), a red-black tree keyed by intervals in which each node keeps the maximum
+// high endpoint in its subtree, so that it can find an interval overlapping a given one in O(lg n) time.
+// As in the book, the tree may hold equal intervals, e.g. two bookings of the same time slot.
+// Each node is keyed by its interval and the sequence number of its insertion, so equal intervals keep their insertion order.
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T: Ord + Clone + Debug, V> {
+    tree: RedBlackTree<(Interval<T>, u64), V, MaxHigh>,
+    next: u64,
+}
+
+impl<T: Ord + Clone + Debug, V> IntervalTree<T, V> {
+    pub fn new() -> Self {
+        IntervalTree { tree: RedBlackTree::default(), next: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    // The value of the earliest inserted interval equal to `i`.
+    pub fn get(&self, i: &Interval<T>) -> Option<&V> {
+        self.first(i).map(|(_, v)| v)
+    }
+
+    // Iterate in order of the low endpoints.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&Interval<T>, &V)> {
+        self.tree.iter().map(|((i, _), v)| (i, v))
+    }
+
+    pub fn validate(&self) {
+        self.tree.validate();
+    }
+
+    // INTERVAL-INSERT, which keeps any equal intervals already in the tree.
+    pub fn interval_insert(&mut self, i: Interval<T>, value: V) {
+        self.tree.insert((i, self.next), value);
+        self.next += 1;
+    }
+
+    // INTERVAL-DELETE, which deletes the earliest inserted interval equal to `i`, returning `None` if there is none.
+    pub fn interval_delete(&mut self, i: &Interval<T>) -> Option<V> {
+        let key = self.first(i)?.0.clone();
+        self.tree.delete(&key)
+    }
+
+    // INTERVAL-SEARCH, which returns an interval overlapping `i`, or `None` if there is none.
+    // If the left subtree cannot contain an overlapping interval, neither can the right one (Theorem 17.2).
+    pub fn interval_search(&self, i: &Interval<T>) -> Option<(&Interval<T>, &V)> {
+        let mut x = self.tree.root_index();
+        while x != NIL && !i.overlaps(&self.tree.key(x).0) {
+            let left = self.tree.left(x);
+            x = if left != NIL && self.tree.data(left).as_ref() >= Some(&i.low) { left } else { self.tree.right(x) };
+        }
+        self.tree.entry(x).map(|((i, _), v)| (i, v))
+    }
+
+    // All intervals overlapping `i` in order of their low endpoints (Exercise 17.3-4),
+    // which only visits subtrees that may contain one, taking O(min(n, k lg n)) time to report k intervals.
+    pub fn interval_search_all(&self, i: &Interval<T>) -> Vec<(&Interval<T>, &V)> {
+        let mut overlaps = Vec::new();
+        self.search_all_aux(self.tree.root_index(), i, &mut overlaps);
+        overlaps
+    }
+
+    fn search_all_aux<'a>(&'a self, x: usize, i: &Interval<T>, overlaps: &mut Vec<(&'a Interval<T>, &'a V)>) {
+        // No interval in the subtree ends at or after `i.low`.
+        if x == NIL || self.tree.data(x).as_ref() < Some(&i.low) { return; }
+        self.search_all_aux(self.tree.left(x), i, overlaps);
+        // Neither this interval nor any in the right subtree starts at or before `i.high`.
+        if self.tree.key(x).0.low > i.high { return; }
+        if i.overlaps(&self.tree.key(x).0) {
+            overlaps.extend(self.tree.entry(x).map(|((i, _), v)| (i, v)));
+        }
+        self.search_all_aux(self.tree.right(x), i, overlaps);
+    }
+
+    // The entry of the earliest inserted interval equal to `i`.
+    fn first(&self, i: &Interval<T>) -> Option<(&(Interval<T>, u64), &V)> {
+        self.tree.range((i.clone(), 0)..=(i.clone(), u64::MAX)).next()
+    }
+}
+
+impl<T: Ord + Clone + Debug, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    // Figure 17.4
+    fn figure_17_4() -> IntervalTree<i32, ()> {
+        let mut t = IntervalTree::new();
+        for (low, high) in [(16, 21), (8, 9), (25, 30), (5, 8), (15, 23), (17, 19), (26, 26), (0, 3), (6, 10), (19, 20)] {
+            t.interval_insert(Interval::new(low, high), ());
+        }
+        t
+    }
+
+    #[test]
+    fn interval_test() {
+        let i = Interval::new(5, 10);
+        assert!(i.overlaps(&Interval::new(10, 12)));
+        assert!(i.overlaps(&Interval::new(6, 7)));
+        assert!(!i.overlaps(&Interval::new(11, 12)));
+        assert_eq!(Interval::from(3..=4), Interval { low: 3, high: 4 });
+        assert!(Interval::new(1, 5) < Interval::new(2, 3) && Interval::new(1, 3) < Interval::new(1, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "low endpoint must not exceed high endpoint")]
+    fn interval_panic_test() {
+        Interval::new(2, 1);
+    }
+
+    #[test]
+    fn interval_tree_test() {
+        let mut t = figure_17_4();
+        t.validate();
+        assert_eq!(t.len(), 10);
+        let found = t.interval_search(&Interval::new(22, 25)).map(|(i, _)| *i);
+        assert!(found.is_some_and(|i| i.overlaps(&Interval::new(22, 25))));
+        assert_eq!(t.interval_search(&Interval::new(11, 14)), None);
+
+        let overlaps = |t: &IntervalTree<i32, ()>, low, high| {
+            t.interval_search_all(&Interval::new(low, high)).into_iter().map(|(i, _)| (i.low, i.high)).collect::<Vec<_>>()
+        };
+        assert_eq!(overlaps(&t, 22, 25), [(15, 23), (25, 30)]);
+        assert_eq!(overlaps(&t, 8, 16), [(5, 8), (6, 10), (8, 9), (15, 23), (16, 21)]);
+        assert_eq!(overlaps(&t, 11, 14), []);
+        assert_eq!(overlaps(&t, 31, 40), []);
+
+        assert_eq!(t.interval_delete(&Interval::new(15, 23)), Some(()));
+        assert_eq!(t.interval_delete(&Interval::new(15, 23)), None);
+        t.validate();
+        assert_eq!(overlaps(&t, 22, 25), [(25, 30)]);
+        assert!(t.iter().map(|(i, _)| i).is_sorted());
+        assert!(IntervalTree::<i32, ()>::default().interval_search(&Interval::new(0, 0)).is_none());
+    }
+
+    #[test]
+    fn interval_tree_duplicate_test() {
+        // Two bookings of the same slot are both kept, in the order they were made.
+        let mut t = IntervalTree::new();
+        let slot = Interval::new(9, 10);
+        t.interval_insert(slot, "alice");
+        t.interval_insert(Interval::new(10, 11), "carol");
+        t.interval_insert(slot, "bob");
+        t.validate();
+        assert_eq!(t.len(), 3);
+        assert_eq!(t.interval_search_all(&Interval::new(8, 9)), [(&slot, &"alice"), (&slot, &"bob")]);
+        assert_eq!(t.get(&slot), Some(&"alice"));
+        assert_eq!(t.iter().rev().map(|(_, &v)| v).collect::<Vec<_>>(), ["carol", "bob", "alice"]);
+
+        assert_eq!(t.interval_delete(&slot), Some("alice"));
+        t.validate();
+        assert_eq!(t.interval_search_all(&Interval::new(8, 9)), [(&slot, &"bob")]);
+        assert_eq!(t.interval_delete(&slot), Some("bob"));
+        assert_eq!(t.interval_delete(&slot), None);
+        assert_eq!(t.len(), 1);
+    }
+
+    // Apply random operations, validating the tree and comparing queries against a brute-force search.
+    #[test]
+    fn interval_tree_random_test() {
+        let mut rng = rand::rng();
+        let mut t = IntervalTree::new();
+        let mut model: Vec<Interval<i32>> = Vec::new();
+        for i in 0..1000 {
+            let low = rng.random_range(0..1000);
+            let interval = Interval::new(low, low + rng.random_range(0..50));
+            if rng.random_bool(0.6) || model.is_empty() {
+                t.interval_insert(interval, i);
+                model.push(interval);
+            } else {
+                let interval = model.swap_remove(rng.random_range(0..model.len()));
+                assert!(t.interval_delete(&interval).is_some());
+            }
+            t.validate();
+            assert_eq!(t.len(), model.len());
+
+            let query = Interval::new(low, low + rng.random_range(0..20));
+            let mut expected: Vec<_> = model.iter().filter(|i| i.overlaps(&query)).copied().collect();
+            expected.sort();
+            let found: Vec<_> = t.interval_search_all(&query).into_iter().map(|(i, _)| *i).collect();
+            assert_eq!(found, expected);
+            match t.interval_search(&query) {
+                Some((i, _)) => assert!(i.overlaps(&query)),
+                None => assert!(expected.is_empty()),
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod order_statistic_tree;
+mod interval_tree;
+
+pub use order_statistic_tree::*;
+pub use interval_tree::*;
\ No newline at end of file
@@ -0,0 +1,167 @@
+use std::cmp::Ordering;
+use crate::ch13::{Augment, RedBlackTree, NIL};
+
+// Subtree sizes, where `x.size = x.left.size + x.right.size + 1` (equation (17.1)).
+#[derive(Debug, Clone)]
+struct Size;
+
+impl<K> Augment<K> for Size {
+    type Data = usize;
+
+    fn nil() -> usize {
+        0
+    }
+
+    fn compute(_: &K, left: &usize, right: &usize) -> usize {
+        left + right + 1
+    }
+}
+
+// Order-statistic tree (section 17.1), a red-black tree in which each node keeps the size of its subtree,
+// so that it can find the entry of a given rank and the rank of a given key in O(lg n) time.
+// Ranks are 0-based, unlike the book, so the minimum has rank 0.
+#[derive(Debug, Clone)]
+pub struct OrderStatisticTree<K, V> {
+    tree: RedBlackTree<K, V, Size>,
+}
+
+impl<K: Ord, V> OrderStatisticTree<K, V> {
+    pub fn new() -> Self {
+        OrderStatisticTree { tree: RedBlackTree::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.tree.contains_key(k)
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.tree.get(k)
+    }
+
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        self.tree.get_mut(k)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.tree.insert(key, value)
+    }
+
+    pub fn delete(&mut self, k: &K) -> Option<V> {
+        self.tree.delete(k)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.tree.iter()
+    }
+
+    pub fn validate(&self) {
+        self.tree.validate();
+    }
+
+    // OS-SELECT, which returns the entry with the `i`th smallest key, or `None` if `i >= len`.
+    pub fn os_select(&self, mut i: usize) -> Option<(&K, &V)> {
+        if i >= self.len() { return None; }
+        let mut x = self.tree.root_index();
+        loop {
+            let r = *self.tree.data(self.tree.left(x));
+            match i.cmp(&r) {
+                Ordering::Equal => return self.tree.entry(x),
+                Ordering::Less => x = self.tree.left(x),
+                Ordering::Greater => {
+                    i -= r + 1;
+                    x = self.tree.right(x);
+                }
+            }
+        }
+    }
+
+    // OS-RANK, which returns the number of keys less than `k`, or `None` if `k` is absent.
+    // Instead of climbing from the node of `k` to the root, it adds up the same sizes on the way down.
+    pub fn os_rank(&self, k: &K) -> Option<usize> {
+        let mut x = self.tree.root_index();
+        let mut r = 0;
+        while x != NIL {
+            let left = self.tree.left(x);
+            match k.cmp(self.tree.key(x)) {
+                Ordering::Equal => return Some(r + self.tree.data(left)),
+                Ordering::Less => x = left,
+                Ordering::Greater => {
+                    r += self.tree.data(left) + 1;
+                    x = self.tree.right(x);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<K: Ord, V> Default for OrderStatisticTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn order_statistic_tree_test() {
+        // The keys of Figure 17.1
+        let mut t = OrderStatisticTree::new();
+        for k in [26, 17, 41, 14, 21, 30, 47, 10, 16, 19, 23, 28, 38, 7, 12, 15, 20, 35, 39, 3] {
+            t.insert(k, ());
+        }
+        t.validate();
+        assert_eq!(t.len(), 20);
+        assert_eq!(t.os_select(0), Some((&3, &())));
+        assert_eq!(t.os_select(16), Some((&38, &()))); // OS-SELECT(T.root, 17)
+        assert_eq!(t.os_select(19), Some((&47, &())));
+        assert_eq!(t.os_select(20), None);
+        assert_eq!(t.os_rank(&38), Some(16)); // The rank of 38 is 17 in the book.
+        assert_eq!(t.os_rank(&3), Some(0));
+        assert_eq!(t.os_rank(&4), None);
+
+        assert_eq!(t.delete(&3), Some(()));
+        t.validate();
+        assert_eq!(t.os_select(0), Some((&7, &())));
+        assert_eq!(t.os_rank(&38), Some(15));
+        assert!(OrderStatisticTree::<i32, ()>::default().os_select(0).is_none());
+    }
+
+    // Apply random operations, validating the tree and comparing every rank against a model after each of them.
+    #[test]
+    fn order_statistic_tree_random_test() {
+        let mut rng = rand::rng();
+        let mut t = OrderStatisticTree::new();
+        let mut model = BTreeMap::new();
+        for i in 0..1000 {
+            let k = rng.random_range(0..200);
+            if rng.random_bool(0.6) {
+                assert_eq!(t.insert(k, i), model.insert(k, i));
+            } else {
+                assert_eq!(t.delete(&k), model.remove(&k));
+            }
+            t.validate();
+            assert_eq!(t.os_rank(&k), model.contains_key(&k).then(|| model.range(..k).count()));
+            if !model.is_empty() {
+                let r = rng.random_range(0..model.len());
+                assert_eq!(t.os_select(r), model.iter().nth(r));
+            }
+        }
+        for (r, (k, _)) in t.iter().enumerate() {
+            assert_eq!(t.os_rank(k), Some(r));
+            assert_eq!(t.os_select(r).map(|(k, _)| k), Some(k));
+        }
+    }
+}
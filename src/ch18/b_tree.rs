@@ -0,0 +1,516 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::io;
+use std::mem;
+use std::path::Path;
+use crate::ch18::{BTreeNode, FilePageStore, FixedSize, IoStats, MemoryPageStore, PageId, PageStore};
+
+// What to delete from a subtree
+#[derive(Debug, Clone, Copy)]
+enum Target<'a, K> {
+    Key(&'a K),
+    Minimum,
+    Maximum,
+}
+
+// B-tree of minimum degree `T` (chapter 18), whose nodes are kept in the pages of a `PageStore`.
+// Every node other than the root has between T - 1 and 2T - 1 keys, and every leaf has the same depth,
+// so a B-tree with n keys has height at most log_T((n + 1) / 2) (Theorem 18.1).
+// As in the book, the root is always kept in main memory, so it is written whenever it changes but never read,
+// and `stats` counts the pages read and written to measure the disk accesses of each operation.
+// Insertion and deletion both go down the tree in a single pass, splitting full nodes on the way down,
+// and making sure that each node descended into has at least T keys, respectively.
+#[derive(Debug)]
+pub struct BTree<K, V, const T: usize, S = MemoryPageStore<K, V>> {
+    store: S,
+    root: PageId,
+    root_node: BTreeNode<K, V>,
+    len: usize,
+    height: usize,
+    stats: Cell<IoStats>,
+}
+
+impl<K: Ord + Clone, V: Clone, const T: usize> BTree<K, V, T> {
+    pub fn new() -> Self {
+        Self::with_store(MemoryPageStore::new())
+    }
+}
+
+impl<K: Ord + Clone + FixedSize, V: Clone + FixedSize, const T: usize> BTree<K, V, T, FilePageStore<K, V, T>> {
+    // Create an empty B-tree in a new page file at `path`, whose pages fit nodes of minimum degree `T`.
+    pub fn with_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::with_store(FilePageStore::create(path)?))
+    }
+}
+
+impl<K: Ord + Clone, V: Clone, const T: usize, S: PageStore<K, V>> BTree<K, V, T, S> {
+    // B-TREE-CREATE, which writes an empty root to a page of `store`.
+    pub fn with_store(mut store: S) -> Self {
+        assert!(T >= 2, "minimum degree must be at least 2");
+        let root = store.allocate();
+        let root_node = BTreeNode::new(true);
+        store.write(root, &root_node);
+        BTree { store, root, root_node, len: 0, height: 0, stats: Cell::new(IoStats { reads: 0, writes: 1 }) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Number of edges on every simple path from the root down to a leaf
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn stats(&self) -> IoStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(IoStats::default());
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.search(k).is_some()
+    }
+
+    // B-TREE-SEARCH, which reads at most `height` pages.
+    pub fn search(&self, k: &K) -> Option<V> {
+        let mut page;
+        let mut x = &self.root_node;
+        loop {
+            match x.keys.binary_search_by(|(key, _)| key.cmp(k)) {
+                Ok(i) => return Some(x.keys[i].1.clone()),
+                Err(_) if x.leaf => return None,
+                Err(i) => {
+                    page = self.disk_read(x.children[i]);
+                    x = &page;
+                }
+            }
+        }
+    }
+
+    // B-TREE-INSERT, which replaces the value and returns the old one if `key` is already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut x_id = self.root;
+        let mut x = self.root_node.clone();
+        if x.keys.len() == 2 * T - 1 {
+            // B-TREE-SPLIT-ROOT, which is the only way the height grows.
+            let s = self.store.allocate();
+            let mut s_node = BTreeNode { leaf: false, keys: Vec::new(), children: vec![self.root] };
+            self.root = s;
+            self.height += 1;
+            self.split_child(s, &mut s_node, 0, x);
+            x_id = s;
+            x = s_node;
+        }
+        let old = self.insert_nonfull(x_id, x, key, value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    // B-TREE-DELETE, returning `None` if `k` is absent.
+    pub fn delete(&mut self, k: &K) -> Option<V> {
+        let entry = self.delete_aux(self.root, self.root_node.clone(), Target::Key(k));
+        if self.root_node.keys.is_empty() && !self.root_node.leaf {
+            // The root lost its last key to a merge of its two children, which becomes the new root.
+            // This may happen even if `k` turns out to be absent.
+            let (old_root, child) = (self.root, self.root_node.children[0]);
+            self.root_node = self.disk_read(child);
+            self.root = child;
+            self.store.free(old_root);
+            self.height -= 1;
+        }
+        let (_, v) = entry?;
+        self.len -= 1;
+        Some(v)
+    }
+
+    // All entries in sorted order, reading every page.
+    pub fn entries(&self) -> Vec<(K, V)> {
+        let mut entries = Vec::with_capacity(self.len);
+        self.entries_aux(&self.root_node, &mut entries);
+        entries
+    }
+
+    // Check every B-tree property, without counting the pages it reads.
+    // Panics with a description of the first violation found.
+    pub fn validate(&self) {
+        let n = self.validate_aux(&self.root_node, self.root, 0, None, None);
+        assert!(n == self.len, "{n} keys are reachable from the root, but the tree has {}", self.len);
+    }
+
+    fn disk_read(&self, id: PageId) -> BTreeNode<K, V> {
+        if id == self.root { return self.root_node.clone(); }
+        let mut stats = self.stats.get();
+        stats.reads += 1;
+        self.stats.set(stats);
+        self.store.read(id)
+    }
+
+    fn disk_write(&mut self, id: PageId, node: &BTreeNode<K, V>) {
+        if id == self.root {
+            self.root_node = node.clone();
+        }
+        let mut stats = self.stats.get();
+        stats.writes += 1;
+        self.stats.set(stats);
+        self.store.write(id, node);
+    }
+
+    // B-TREE-SPLIT-CHILD, which splits the full child `y` of `x` around its median key,
+    // moves the median up into `x`, and returns the two halves.
+    fn split_child(&mut self, x_id: PageId, x: &mut BTreeNode<K, V>, i: usize, mut y: BTreeNode<K, V>) -> (BTreeNode<K, V>, BTreeNode<K, V>) {
+        let y_id = x.children[i];
+        let z_id = self.store.allocate();
+        let mut z = BTreeNode::new(y.leaf);
+        z.keys = y.keys.split_off(T);
+        if !y.leaf {
+            z.children = y.children.split_off(T);
+        }
+        let median = y.keys.pop().unwrap();
+        x.keys.insert(i, median);
+        x.children.insert(i + 1, z_id);
+        self.disk_write(y_id, &y);
+        self.disk_write(z_id, &z);
+        self.disk_write(x_id, x);
+        (y, z)
+    }
+
+    // B-TREE-INSERT-NONFULL, iteratively.
+    fn insert_nonfull(&mut self, mut x_id: PageId, mut x: BTreeNode<K, V>, key: K, value: V) -> Option<V> {
+        loop {
+            let mut i = match x.keys.binary_search_by(|(k, _)| k.cmp(&key)) {
+                Ok(i) => {
+                    let old = mem::replace(&mut x.keys[i].1, value);
+                    self.disk_write(x_id, &x);
+                    return Some(old);
+                }
+                Err(i) => i,
+            };
+            if x.leaf {
+                x.keys.insert(i, (key, value));
+                self.disk_write(x_id, &x);
+                return None;
+            }
+            let mut c = self.disk_read(x.children[i]);
+            if c.keys.len() == 2 * T - 1 {
+                let (y, z) = self.split_child(x_id, &mut x, i, c);
+                c = match key.cmp(&x.keys[i].0) {
+                    Ordering::Less => y,
+                    Ordering::Greater => {
+                        i += 1;
+                        z
+                    }
+                    Ordering::Equal => {
+                        let old = mem::replace(&mut x.keys[i].1, value);
+                        self.disk_write(x_id, &x);
+                        return Some(old);
+                    }
+                };
+            }
+            x_id = x.children[i];
+            x = c;
+        }
+    }
+
+    // Delete `target` from the subtree rooted at `x`, which has at least T keys unless it is the root,
+    // and return the deleted entry, or `None` if `target` is absent.
+    fn delete_aux(&mut self, mut x_id: PageId, mut x: BTreeNode<K, V>, target: Target<K>) -> Option<(K, V)> {
+        loop {
+            let location = match target {
+                Target::Key(k) => x.keys.binary_search_by(|(key, _)| key.cmp(k)),
+                Target::Minimum if x.leaf => Ok(0),
+                Target::Minimum => Err(0),
+                Target::Maximum if x.leaf => Ok(x.keys.len() - 1),
+                Target::Maximum => Err(x.keys.len()),
+            };
+            match location {
+                Ok(i) if x.leaf => {
+                    // Case 1
+                    let entry = x.keys.remove(i);
+                    self.disk_write(x_id, &x);
+                    return Some(entry);
+                }
+                Ok(i) => {
+                    let (y_id, z_id) = (x.children[i], x.children[i + 1]);
+                    let y = self.disk_read(y_id);
+                    if y.keys.len() >= T {
+                        // Case 2a, which replaces the key with its predecessor.
+                        let predecessor = self.delete_aux(y_id, y, Target::Maximum).unwrap();
+                        let entry = mem::replace(&mut x.keys[i], predecessor);
+                        self.disk_write(x_id, &x);
+                        return Some(entry);
+                    }
+                    let z = self.disk_read(z_id);
+                    if z.keys.len() >= T {
+                        // Case 2b, which replaces the key with its successor.
+                        let successor = self.delete_aux(z_id, z, Target::Minimum).unwrap();
+                        let entry = mem::replace(&mut x.keys[i], successor);
+                        self.disk_write(x_id, &x);
+                        return Some(entry);
+                    }
+                    // Case 2c, which merges the key and `z` into `y` and deletes the key from `y`.
+                    x = self.merge_children(x_id, &mut x, i, y, z);
+                    x_id = y_id;
+                }
+                Err(_) if x.leaf => return None,
+                Err(i) => (x_id, x) = self.ensure_child(x_id, &mut x, i),
+            }
+        }
+    }
+
+    // Case 3, which makes sure that child `i` of `x` has at least T keys before descending into it
+    // by moving a key from a sibling through `x` (case 3a) or by merging it with a sibling (case 3b),
+    // and returns the child.
+    fn ensure_child(&mut self, x_id: PageId, x: &mut BTreeNode<K, V>, i: usize) -> (PageId, BTreeNode<K, V>) {
+        let c_id = x.children[i];
+        let mut c = self.disk_read(c_id);
+        if c.keys.len() >= T { return (c_id, c); }
+
+        if i > 0 {
+            let l_id = x.children[i - 1];
+            let mut l = self.disk_read(l_id);
+            if l.keys.len() >= T {
+                let entry = mem::replace(&mut x.keys[i - 1], l.keys.pop().unwrap());
+                c.keys.insert(0, entry);
+                if !c.leaf {
+                    c.children.insert(0, l.children.pop().unwrap());
+                }
+                self.disk_write(l_id, &l);
+                self.disk_write(c_id, &c);
+                self.disk_write(x_id, x);
+                return (c_id, c);
+            }
+            if i == x.keys.len() {
+                // No right sibling
+                return (l_id, self.merge_children(x_id, x, i - 1, l, c));
+            }
+        }
+
+        let r_id = x.children[i + 1];
+        let mut r = self.disk_read(r_id);
+        if r.keys.len() >= T {
+            let entry = mem::replace(&mut x.keys[i], r.keys.remove(0));
+            c.keys.push(entry);
+            if !c.leaf {
+                c.children.push(r.children.remove(0));
+            }
+            self.disk_write(r_id, &r);
+            self.disk_write(c_id, &c);
+            self.disk_write(x_id, x);
+            return (c_id, c);
+        }
+        (c_id, self.merge_children(x_id, x, i, c, r))
+    }
+
+    // Merge key `i` of `x` and its right child `z` into its left child `y`, which has 2T - 1 keys afterwards,
+    // and free the page of `z`.
+    fn merge_children(&mut self, x_id: PageId, x: &mut BTreeNode<K, V>, i: usize, mut y: BTreeNode<K, V>, z: BTreeNode<K, V>) -> BTreeNode<K, V> {
+        let (y_id, z_id) = (x.children[i], x.children[i + 1]);
+        y.keys.push(x.keys.remove(i));
+        y.keys.extend(z.keys);
+        y.children.extend(z.children);
+        x.children.remove(i + 1);
+        self.store.free(z_id);
+        self.disk_write(y_id, &y);
+        self.disk_write(x_id, x);
+        y
+    }
+
+    fn entries_aux(&self, x: &BTreeNode<K, V>, entries: &mut Vec<(K, V)>) {
+        for (i, entry) in x.keys.iter().enumerate() {
+            if !x.leaf {
+                self.entries_aux(&self.disk_read(x.children[i]), entries);
+            }
+            entries.push(entry.clone());
+        }
+        if let Some(&last) = x.children.last() {
+            self.entries_aux(&self.disk_read(last), entries);
+        }
+    }
+
+    // Validate the subtree rooted at `x` at `depth`, whose keys must lie strictly between `lo` and `hi`,
+    // returning its number of keys.
+    fn validate_aux(&self, x: &BTreeNode<K, V>, x_id: PageId, depth: usize, lo: Option<&K>, hi: Option<&K>) -> usize {
+        let n = x.keys.len();
+        assert!(n < 2 * T, "node {x_id} has {n} keys");
+        assert!(x_id == self.root || n >= T - 1, "node {x_id} has {n} keys");
+        assert!(x.keys.windows(2).all(|w| w[0].0 < w[1].0), "keys of node {x_id} are out of order");
+        let (first, last) = (&x.keys.first().map(|e| &e.0), &x.keys.last().map(|e| &e.0));
+        assert!(lo.is_none_or(|lo| first.is_none_or(|k| lo < k)), "node {x_id} is out of order");
+        assert!(hi.is_none_or(|hi| last.is_none_or(|k| k < hi)), "node {x_id} is out of order");
+        if x.leaf {
+            assert!(x.children.is_empty(), "leaf {x_id} has children");
+            assert!(depth == self.height, "leaf {x_id} has depth {depth} instead of {}", self.height);
+            return n;
+        }
+        assert!(x.children.len() == n + 1, "node {x_id} has {n} keys and {} children", x.children.len());
+        let mut count = n;
+        for (i, &c) in x.children.iter().enumerate() {
+            let lo = if i == 0 { lo } else { Some(&x.keys[i - 1].0) };
+            let hi = if i == n { hi } else { Some(&x.keys[i].0) };
+            count += self.validate_aux(&self.store.read(c), c, depth + 1, lo, hi);
+        }
+        count
+    }
+}
+
+impl<K: Ord + Clone, V: Clone, const T: usize> Default for BTree<K, V, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::env;
+    use std::fs;
+    use rand::Rng;
+    use rand::seq::SliceRandom;
+    use super::*;
+
+    fn keys<S: PageStore<char, ()>>(t: &BTree<char, (), 3, S>) -> String {
+        t.entries().into_iter().map(|(k, _)| k).collect()
+    }
+
+    #[test]
+    fn b_tree_test() {
+        // The keys of Figure 18.7, inserted in sorted order rather than forming the initial tree of the figure
+        let mut t = BTree::<char, (), 3>::new();
+        for k in "ACDEGJKMNOPRSTUVXYZ".chars() {
+            t.insert(k, ());
+            t.validate();
+        }
+        assert_eq!((t.len(), t.height()), (19, 2));
+        // The insertions of Figure 18.7
+        for k in ['B', 'Q', 'L', 'F'] {
+            assert_eq!(t.insert(k, ()), None);
+            t.validate();
+        }
+        assert_eq!(keys(&t), "ABCDEFGJKLMNOPQRSTUVXYZ");
+        assert_eq!(t.insert('Q', ()), Some(()));
+        assert!(t.contains_key(&'Q') && !t.contains_key(&'W'));
+
+        // The deletions of Figure 18.8
+        for k in ['F', 'M', 'G', 'D', 'B'] {
+            assert_eq!(t.delete(&k), Some(()));
+            t.validate();
+        }
+        assert_eq!(t.delete(&'B'), None);
+        assert_eq!(keys(&t), "ACEJKLNOPQRSTUVXYZ");
+        assert_eq!(t.len(), 18);
+    }
+
+    #[test]
+    fn b_tree_map_test() {
+        let mut t = BTree::<i32, String, 2>::default();
+        assert!(t.is_empty() && t.search(&1).is_none() && t.delete(&1).is_none());
+        for k in 0..100 {
+            t.insert(k, k.to_string());
+        }
+        assert_eq!(t.insert(42, String::from("answer")), Some(String::from("42")));
+        assert_eq!(t.search(&42), Some(String::from("answer")));
+        assert_eq!(t.delete(&42), Some(String::from("answer")));
+        assert_eq!(t.search(&42), None);
+        for k in (0..100).rev() {
+            t.delete(&k);
+            t.validate();
+        }
+        assert!(t.is_empty() && t.height() == 0);
+        assert_eq!(t.store().pages(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "minimum degree must be at least 2")]
+    fn b_tree_degree_test() {
+        BTree::<i32, (), 1>::new();
+    }
+
+    // A search reads one page per level below the root, and an insertion or deletion accesses O(1) pages per level.
+    #[test]
+    fn b_tree_io_test() {
+        let mut rng = rand::rng();
+        let mut keys: Vec<u32> = (0..10000).collect();
+        keys.shuffle(&mut rng);
+        let mut t = BTree::<u32, (), 8>::new();
+        for &k in &keys {
+            t.reset_stats();
+            t.insert(k, ());
+            let h = t.height() as u64;
+            assert!(t.stats().reads <= h && t.stats().writes <= 3 * h + 1);
+        }
+        // Theorem 18.1
+        let h = t.height();
+        assert!(h >= 1 && h as f64 <= (10001.0f64 / 2.0).log(8.0));
+        for &k in &keys {
+            t.reset_stats();
+            assert!(t.contains_key(&k));
+            assert!(t.stats().reads <= h as u64 && t.stats().writes == 0);
+        }
+        // An unsuccessful search always ends at a leaf.
+        t.reset_stats();
+        assert!(!t.contains_key(&10000));
+        assert_eq!(t.stats(), IoStats { reads: h as u64, writes: 0 });
+        for &k in &keys {
+            t.reset_stats();
+            t.delete(&k);
+            let h = t.height() as u64 + 1;
+            assert!(t.stats().reads <= 4 * h && t.stats().writes <= 4 * h);
+        }
+        assert!(t.is_empty());
+    }
+
+    // Apply random operations, validating the tree and comparing against a model after each of them.
+    #[test]
+    fn b_tree_random_test() {
+        let mut rng = rand::rng();
+        let mut t = BTree::<i32, i32, 2>::new();
+        let mut model = BTreeMap::new();
+        for i in 0..2000 {
+            let k = rng.random_range(0..300);
+            if rng.random_bool(0.6) {
+                assert_eq!(t.insert(k, i), model.insert(k, i));
+            } else {
+                assert_eq!(t.delete(&k), model.remove(&k));
+            }
+            t.validate();
+            assert_eq!(t.len(), model.len());
+            assert_eq!(t.search(&k), model.get(&k).copied());
+        }
+        assert!(t.entries().into_iter().eq(model.into_iter()));
+    }
+
+    #[test]
+    fn b_tree_file_test() {
+        let path = env::temp_dir().join(format!("clrs-b-tree-{}", std::process::id()));
+        let mut t = BTree::<u64, u32, 4, _>::with_file(&path).unwrap();
+        let mut model = BTreeMap::new();
+        let mut rng = rand::rng();
+        for i in 0..1000 {
+            let k = rng.random_range(0..500);
+            if rng.random_bool(0.7) {
+                assert_eq!(t.insert(k, i), model.insert(k, i));
+            } else {
+                assert_eq!(t.delete(&k), model.remove(&k));
+            }
+        }
+        t.validate();
+        assert!(t.entries().into_iter().eq(model.into_iter()));
+        assert!(t.stats().reads > 0 && t.stats().writes > 0);
+        let pages = t.store().pages() as u64;
+        assert!(fs::metadata(&path).unwrap().len() >= pages * FilePageStore::<u64, u32, 4>::PAGE_SIZE as u64);
+        fs::remove_file(path).unwrap();
+    }
+}
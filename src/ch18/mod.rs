@@ -0,0 +1,5 @@
+mod page_store;
+mod b_tree;
+
+pub use page_store::*;
+pub use b_tree::*;
\ No newline at end of file
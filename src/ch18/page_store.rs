@@ -0,0 +1,268 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+// Index of a page, the unit of transfer between main memory and the disk (section 18.1).
+pub type PageId = usize;
+
+// Contents of a B-tree node, which occupies one page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BTreeNode<K, V> {
+    pub leaf: bool,
+    pub keys: Vec<(K, V)>, // `x.n` is `keys.len()`
+    pub children: Vec<PageId>, // Empty for a leaf, and `keys.len() + 1` children otherwise
+}
+
+impl<K, V> BTreeNode<K, V> {
+    pub fn new(leaf: bool) -> Self {
+        BTreeNode { leaf, keys: Vec::new(), children: Vec::new() }
+    }
+}
+
+// Storage of B-tree nodes in pages, where `read` is DISK-READ and `write` is DISK-WRITE.
+pub trait PageStore<K, V> {
+    // ALLOCATE-NODE, which returns a page that is not in use, without reading or writing it.
+    fn allocate(&mut self) -> PageId;
+
+    // Release a page so that `allocate` may reuse it.
+    fn free(&mut self, id: PageId);
+
+    fn read(&self, id: PageId) -> BTreeNode<K, V>;
+
+    fn write(&mut self, id: PageId, node: &BTreeNode<K, V>);
+}
+
+// Numbers of pages read from and written to a `PageStore`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IoStats {
+    pub reads: u64,
+    pub writes: u64,
+}
+
+// Page store that keeps the pages in main memory, so that the transfers are only simulated.
+#[derive(Debug, Clone)]
+pub struct MemoryPageStore<K, V> {
+    pages: Vec<Option<BTreeNode<K, V>>>,
+    free: Vec<PageId>,
+}
+
+impl<K, V> MemoryPageStore<K, V> {
+    pub fn new() -> Self {
+        MemoryPageStore { pages: Vec::new(), free: Vec::new() }
+    }
+
+    // Number of pages in use
+    pub fn pages(&self) -> usize {
+        self.pages.len() - self.free.len()
+    }
+}
+
+impl<K, V> Default for MemoryPageStore<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone, V: Clone> PageStore<K, V> for MemoryPageStore<K, V> {
+    fn allocate(&mut self) -> PageId {
+        self.free.pop().unwrap_or_else(|| {
+            self.pages.push(None);
+            self.pages.len() - 1
+        })
+    }
+
+    fn free(&mut self, id: PageId) {
+        self.pages[id] = None;
+        self.free.push(id);
+    }
+
+    fn read(&self, id: PageId) -> BTreeNode<K, V> {
+        self.pages[id].clone().expect("page is not in use")
+    }
+
+    fn write(&mut self, id: PageId, node: &BTreeNode<K, V>) {
+        self.pages[id] = Some(node.clone());
+    }
+}
+
+// Types encoded in a fixed number of bytes, which `FilePageStore` can lay out in pages.
+pub trait FixedSize: Sized {
+    const SIZE: usize;
+
+    fn encode(&self, buf: &mut [u8]);
+
+    fn decode(buf: &[u8]) -> Self;
+}
+
+macro_rules! impl_fixed_size {
+    ($($t:ty),*) => {
+        $(
+            impl FixedSize for $t {
+                const SIZE: usize = size_of::<$t>();
+
+                fn encode(&self, buf: &mut [u8]) {
+                    buf.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(buf: &[u8]) -> Self {
+                    <$t>::from_le_bytes(buf.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_size!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl FixedSize for () {
+    const SIZE: usize = 0;
+
+    fn encode(&self, _: &mut [u8]) {}
+
+    fn decode(_: &[u8]) {}
+}
+
+// Page store backed by a file of pages of `PAGE_SIZE` bytes, each large enough for a node of a B-tree with minimum degree `T`.
+// A page holds a leaf flag, the number of keys, room for 2T - 1 entries and room for 2T children.
+// Pages freed by a deletion are reused by later allocations but the file never shrinks.
+// Panics if the file cannot be read or written.
+#[derive(Debug)]
+pub struct FilePageStore<K, V, const T: usize> {
+    file: File,
+    pages: usize,
+    free: Vec<PageId>,
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K: FixedSize, V: FixedSize, const T: usize> FilePageStore<K, V, T> {
+    pub const PAGE_SIZE: usize = Self::CHILDREN + 2 * T * 8;
+
+    const ENTRIES: usize = 5;
+    const CHILDREN: usize = Self::ENTRIES + (2 * T - 1) * (K::SIZE + V::SIZE);
+
+    // Create an empty page file at `path`, truncating any existing file.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::options().read(true).write(true).create(true).truncate(true).open(path)?;
+        Ok(FilePageStore { file, pages: 0, free: Vec::new(), marker: PhantomData })
+    }
+
+    // Number of pages in use
+    pub fn pages(&self) -> usize {
+        self.pages - self.free.len()
+    }
+
+    fn encode(node: &BTreeNode<K, V>, buf: &mut [u8]) {
+        let n = node.keys.len();
+        assert!(n < 2 * T, "node with {n} keys does not fit in a page");
+        buf[0] = node.leaf as u8;
+        buf[1..Self::ENTRIES].copy_from_slice(&(n as u32).to_le_bytes());
+        let mut at = Self::ENTRIES;
+        for (k, v) in &node.keys {
+            k.encode(&mut buf[at..at + K::SIZE]);
+            v.encode(&mut buf[at + K::SIZE..at + K::SIZE + V::SIZE]);
+            at += K::SIZE + V::SIZE;
+        }
+        for (c, buf) in node.children.iter().zip(buf[Self::CHILDREN..].chunks_exact_mut(8)) {
+            buf.copy_from_slice(&(*c as u64).to_le_bytes());
+        }
+    }
+
+    fn decode(buf: &[u8]) -> BTreeNode<K, V> {
+        let leaf = buf[0] != 0;
+        let n = u32::from_le_bytes(buf[1..Self::ENTRIES].try_into().unwrap()) as usize;
+        let keys = buf[Self::ENTRIES..].chunks_exact(K::SIZE + V::SIZE).take(n)
+            .map(|entry| (K::decode(&entry[..K::SIZE]), V::decode(&entry[K::SIZE..])))
+            .collect();
+        let children = if leaf { Vec::new() } else {
+            buf[Self::CHILDREN..].chunks_exact(8).take(n + 1)
+                .map(|c| u64::from_le_bytes(c.try_into().unwrap()) as PageId)
+                .collect()
+        };
+        BTreeNode { leaf, keys, children }
+    }
+
+    fn seek(&self, id: PageId) -> io::Result<&File> {
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start((id * Self::PAGE_SIZE) as u64))?;
+        Ok(file)
+    }
+}
+
+impl<K: FixedSize, V: FixedSize, const T: usize> PageStore<K, V> for FilePageStore<K, V, T> {
+    fn allocate(&mut self) -> PageId {
+        self.free.pop().unwrap_or_else(|| {
+            self.pages += 1;
+            self.pages - 1
+        })
+    }
+
+    fn free(&mut self, id: PageId) {
+        self.free.push(id);
+    }
+
+    fn read(&self, id: PageId) -> BTreeNode<K, V> {
+        let mut buf = vec![0; Self::PAGE_SIZE];
+        self.seek(id).and_then(|mut file| file.read_exact(&mut buf))
+            .unwrap_or_else(|e| panic!("failed to read page {id}: {e}"));
+        Self::decode(&buf)
+    }
+
+    fn write(&mut self, id: PageId, node: &BTreeNode<K, V>) {
+        let mut buf = vec![0; Self::PAGE_SIZE];
+        Self::encode(node, &mut buf);
+        self.seek(id).and_then(|mut file| file.write_all(&buf))
+            .unwrap_or_else(|e| panic!("failed to write page {id}: {e}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use super::*;
+
+    #[test]
+    fn memory_page_store_test() {
+        let mut store = MemoryPageStore::new();
+        let a = store.allocate();
+        let b = store.allocate();
+        let node = BTreeNode { leaf: true, keys: vec![(1, 'a'), (2, 'b')], children: Vec::new() };
+        store.write(a, &node);
+        store.write(b, &BTreeNode::new(true));
+        assert_eq!(store.read(a), node);
+        assert_eq!(store.pages(), 2);
+        store.free(a);
+        assert_eq!(store.pages(), 1);
+        assert_eq!(store.allocate(), a);
+    }
+
+    #[test]
+    fn file_page_store_test() {
+        let path = env::temp_dir().join(format!("clrs-page-store-{}", std::process::id()));
+        let mut store = FilePageStore::<u32, i64, 2>::create(&path).unwrap();
+        assert_eq!(FilePageStore::<u32, i64, 2>::PAGE_SIZE, 5 + 3 * 12 + 4 * 8);
+        let leaf = BTreeNode { leaf: true, keys: vec![(7, -7), (9, -9)], children: Vec::new() };
+        let internal = BTreeNode { leaf: false, keys: vec![(1, 1), (2, 2), (3, 3)], children: vec![4, 3, 2, 1] };
+        let (a, b) = (store.allocate(), store.allocate());
+        store.write(b, &internal);
+        store.write(a, &leaf);
+        assert_eq!(store.read(a), leaf);
+        assert_eq!(store.read(b), internal);
+        store.write(a, &BTreeNode::new(true));
+        assert_eq!(store.read(a), BTreeNode::new(true));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 2 * FilePageStore::<u32, i64, 2>::PAGE_SIZE as u64);
+        store.free(a);
+        assert_eq!((store.pages(), store.allocate()), (1, a));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn fixed_size_test() {
+        let mut buf = [0; 8];
+        (-2i64).encode(&mut buf);
+        assert_eq!(i64::decode(&buf), -2);
+        1.5f32.encode(&mut buf[..4]);
+        assert_eq!(f32::decode(&buf[..4]), 1.5);
+        assert_eq!(<() as FixedSize>::SIZE, 0);
+    }
+}
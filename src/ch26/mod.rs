@@ -0,0 +1,3 @@
+mod p_merge_sort;
+
+pub use p_merge_sort::*;
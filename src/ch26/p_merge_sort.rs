@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+use std::mem;
+use std::thread;
+use crate::ch02;
+use crate::utils;
+
+/*
+    Parallel Merge Sort
+    P-MERGE-SORT sorts both halves in parallel, then P-MERGE merges them in parallel:
+    take the median `x` of the longer run, find the split point of `x` in the shorter run by binary search,
+    place `x` at its final position, and merge the two pairs of sub-runs on either side of `x` in parallel.
+
+    Subarrays of at most `cutoff` elements are sorted by `insertion_sort` and merged serially,
+    since spawning a thread costs far more than sorting or merging a few elements.
+    Each spawn makes a new OS thread rather than a task for a scheduler, so the recursion only spawns
+    in its top ⌈lg P⌉ levels, where P is `available_parallelism`, and runs serially below them.
+    At most 2^⌈lg P⌉ < 2P threads then run at a time, however small `cutoff` is.
+*/
+pub fn p_merge_sort<T: PartialOrd + Clone + Send>(a: &mut [T], cutoff: usize) {
+    p_merge_sort_by(a, cutoff, utils::partial_compare);
+}
+
+// The sort is stable: equal elements keep their relative order.
+pub fn p_merge_sort_by<T, F>(a: &mut [T], cutoff: usize, compare: F)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let cutoff = cutoff.max(1);
+    if a.len() <= cutoff {
+        ch02::insertion_sort_by(a, compare);
+        return;
+    }
+    let mut b = a.to_vec();
+    p_merge_sort_aux(a, &mut b, true, cutoff, fork_depth(), &compare);
+}
+
+// The number of levels of the recursion that spawn a thread, which is ⌈lg P⌉.
+fn fork_depth() -> u32 {
+    thread::available_parallelism().map_or(1, |p| p.get()).next_power_of_two().ilog2()
+}
+
+// Run `f` and `g`, in parallel if `fork` is true.
+fn join<A, B>(fork: bool, f: A, g: B)
+where
+    A: FnOnce() + Send,
+    B: FnOnce(),
+{
+    if fork {
+        thread::scope(|s| {
+            s.spawn(f);
+            g();
+        });
+    } else {
+        f();
+        g();
+    }
+}
+
+// Sort the elements of `a`, leaving the result in `a` if `in_a` is true, or in `b` otherwise.
+// Merging swaps elements between `a` and `b`, so each level of the recursion merges in the opposite direction.
+// Only the top `depth` levels spawn threads. The merge at a level gets the same `depth`,
+// since the threads of the sorts below it have finished by the time it starts.
+fn p_merge_sort_aux<T, F>(a: &mut [T], b: &mut [T], in_a: bool, cutoff: usize, depth: u32, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let n = a.len();
+    if n <= cutoff {
+        ch02::insertion_sort_by(a, compare);
+        if !in_a { b.swap_with_slice(a); }
+        return;
+    }
+
+    let q = n / 2;
+    {
+        let (a_left, a_right) = a.split_at_mut(q);
+        let (b_left, b_right) = b.split_at_mut(q);
+        let below = depth.saturating_sub(1);
+        join(
+            depth > 0,
+            || p_merge_sort_aux(a_left, b_left, !in_a, cutoff, below, compare),
+            || p_merge_sort_aux(a_right, b_right, !in_a, cutoff, below, compare),
+        );
+    }
+
+    if in_a {
+        let (b_left, b_right) = b.split_at_mut(q);
+        p_merge(b_left, b_right, a, cutoff, depth, compare);
+    } else {
+        let (a_left, a_right) = a.split_at_mut(q);
+        p_merge(a_left, a_right, b, cutoff, depth, compare);
+    }
+}
+
+// Merge the sorted runs `left` and `right` into `dst` by swapping, where `dst.len() == left.len() + right.len()`.
+// Only the top `depth` levels of the recursion spawn threads.
+fn p_merge<T, F>(left: &mut [T], right: &mut [T], dst: &mut [T], cutoff: usize, depth: u32, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if left.is_empty() {
+        dst.swap_with_slice(right);
+        return;
+    }
+    if right.is_empty() {
+        dst.swap_with_slice(left);
+        return;
+    }
+    if dst.len() <= cutoff {
+        merge(left, right, dst, compare);
+        return;
+    }
+
+    // To keep the merge stable, elements of `right` equal to `x` go after `x` if `x` is taken from `left`,
+    // and elements of `left` equal to `x` go before `x` if `x` is taken from `right`.
+    let x_in_left = left.len() >= right.len();
+    let (q1, q2) = if x_in_left {
+        let q1 = left.len() / 2;
+        (q1, find_split_point(right, &left[q1], |y, x| compare(y, x) == Ordering::Less))
+    } else {
+        let q2 = right.len() / 2;
+        (find_split_point(left, &right[q2], |y, x| compare(y, x) != Ordering::Greater), q2)
+    };
+    let q3 = q1 + q2;
+
+    let (left_lo, left_hi) = left.split_at_mut(q1);
+    let (right_lo, right_hi) = right.split_at_mut(q2);
+    let (dst_lo, dst_hi) = dst.split_at_mut(q3);
+    let (dst_x, dst_hi) = dst_hi.split_first_mut().unwrap();
+    let (x, left_hi, right_hi) = if x_in_left {
+        let (x, left_hi) = left_hi.split_first_mut().unwrap();
+        (x, left_hi, right_hi)
+    } else {
+        let (x, right_hi) = right_hi.split_first_mut().unwrap();
+        (x, left_hi, right_hi)
+    };
+    mem::swap(dst_x, x);
+
+    let below = depth.saturating_sub(1);
+    join(
+        depth > 0,
+        || p_merge(left_lo, right_lo, dst_lo, cutoff, below, compare),
+        || p_merge(left_hi, right_hi, dst_hi, cutoff, below, compare),
+    );
+}
+
+// Return the number of leading elements `y` of the sorted run `a` for which `goes_before(y, x)` holds.
+fn find_split_point<T, P>(a: &[T], x: &T, goes_before: P) -> usize
+where
+    P: Fn(&T, &T) -> bool,
+{
+    let mut low = 0;
+    let mut high = a.len();
+    while low < high {
+        let mid = (low + high) / 2;
+        if goes_before(&a[mid], x) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+fn merge<T, F>(left: &mut [T], right: &mut [T], dst: &mut [T], compare: &F)
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    let mut i = 0;
+    let mut j = 0;
+    for d in dst.iter_mut() {
+        if j == right.len() || (i < left.len() && compare(&left[i], &right[j]) != Ordering::Greater) {
+            mem::swap(d, &mut left[i]);
+            i += 1;
+        } else {
+            mem::swap(d, &mut right[j]);
+            j += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::ch02;
+    use super::*;
+
+    #[test]
+    fn p_merge_sort_test() {
+        ch02::tests::sort_i32(|a| p_merge_sort(a, 0));
+        ch02::tests::sort_i32(|a| p_merge_sort(a, 1));
+        ch02::tests::sort_i32(|a| p_merge_sort(a, 2));
+        ch02::tests::sort_i32(|a| p_merge_sort(a, 3));
+        ch02::tests::sort_i32(|a| p_merge_sort(a, 64));
+        ch02::tests::sort_f64(|a| p_merge_sort(a, 1));
+        ch02::tests::sort_char(|a| p_merge_sort(a, 2));
+        ch02::tests::sort_str(|a| p_merge_sort(a, 1));
+        ch02::tests::sort_string(|a| p_merge_sort(a, 2));
+        ch02::tests::sort_by_i32(|a, compare| p_merge_sort_by(a, 1, compare));
+        ch02::tests::sort_by_struct(|a, compare| p_merge_sort_by(a, 2, compare));
+    }
+
+    #[test]
+    fn p_merge_sort_stable_test() {
+        ch02::tests::sort_by_key_stable(|a, key| p_merge_sort_by(a, 1, |x, y| key(x).cmp(&key(y))));
+        ch02::tests::sort_by_key_stable(|a, key| p_merge_sort_by(a, 8, |x, y| key(x).cmp(&key(y))));
+    }
+
+    #[test]
+    fn p_merge_sort_random_test() {
+        let mut rng = rand::rng();
+        for (n, cutoff) in [(10, 1), (100, 1), (1000, 16), (1000, 100), (100_000, 4096)] {
+            let mut a: Vec<(u32, usize)> = (0..n).map(|i| (rng.random_range(0..n as u32 / 4 + 1), i)).collect();
+            let mut expected = a.clone();
+            ch02::merge_sort_by_key(&mut expected, |&(key, _)| key);
+            p_merge_sort_by(&mut a, cutoff, |x, y| x.0.cmp(&y.0));
+            assert_eq!(a, expected, "n: {n}, cutoff: {cutoff}");
+        }
+    }
+
+    #[test]
+    fn p_merge_sort_tiny_cutoff_test() {
+        // Forking at every level with a cutoff of 1 would make hundreds of thousands of threads block at once.
+        assert!(1 << fork_depth() >= thread::available_parallelism().map_or(1, |p| p.get()));
+        let mut rng = rand::rng();
+        let mut a: Vec<u32> = (0..300_000).map(|_| rng.random()).collect();
+        let mut expected = a.clone();
+        expected.sort();
+        p_merge_sort(&mut a, 1);
+        assert_eq!(a, expected);
+    }
+}
@@ -1,20 +1,227 @@
 mod ch02;
 mod ch04;
 mod ch05;
+mod ch06;
+mod ch07;
+mod ch08;
+mod ch09;
+mod ch10;
+mod ch11;
+mod ch12;
+mod ch13;
+mod ch17;
+mod ch18;
+mod ch26;
 mod utils;
 
-pub mod prelude {
+// Sorting algorithms
+pub mod sort {
     pub use crate::ch02::insertion_sort;
+    pub use crate::ch02::insertion_sort_by;
+    pub use crate::ch02::insertion_sort_by_key;
+    pub use crate::ch02::binary_insertion_sort;
+    pub use crate::ch02::binary_insertion_sort_by;
+    pub use crate::ch02::binary_insertion_sort_by_key;
     pub use crate::ch02::merge_sort;
+    pub use crate::ch02::merge_sort_by;
+    pub use crate::ch02::merge_sort_by_key;
+    pub use crate::ch02::merge_sort_with_buffer;
+    pub use crate::ch02::merge_sort_with_buffer_by;
+    pub use crate::ch02::merge_sort_bottom_up;
+    pub use crate::ch02::merge_sort_bottom_up_by;
+    pub use crate::ch02::merge_insertion_sort;
+    pub use crate::ch02::merge_insertion_sort_by;
+    pub use crate::ch02::merge_insertion_sort_by_key;
+    pub use crate::ch02::merge_insertion_sort_cutoff;
     pub use crate::ch02::bubble_sort;
+    pub use crate::ch02::bubble_sort_by;
+    pub use crate::ch02::bubble_sort_by_key;
+    pub use crate::ch06::heapsort;
+    pub use crate::ch06::heapsort_by;
+    pub use crate::ch06::heapsort_by_key;
+    pub use crate::ch07::quicksort;
+    pub use crate::ch07::quicksort_by;
+    pub use crate::ch07::quicksort_by_key;
+    pub use crate::ch07::randomized_quicksort;
+    pub use crate::ch07::randomized_quicksort_by;
+    pub use crate::ch07::randomized_quicksort_by_key;
+    pub use crate::ch07::quicksort_with;
+    pub use crate::ch07::quicksort_with_by;
+    pub use crate::ch07::PartitionScheme;
+    pub use crate::ch07::Pivot;
+    pub use crate::ch07::partition;
+    pub use crate::ch07::partition_by;
+    pub use crate::ch07::hoare_partition;
+    pub use crate::ch07::hoare_partition_by;
+    pub use crate::ch07::three_way_partition;
+    pub use crate::ch07::three_way_partition_by;
+    pub use crate::ch07::introsort;
+    pub use crate::ch07::introsort_by;
+    pub use crate::ch07::introsort_by_key;
+    pub use crate::ch08::counting_sort;
+    pub use crate::ch08::counting_sort_by_key;
+    pub use crate::ch08::radix_sort;
+    pub use crate::ch08::radix_sort_by_key;
+    pub use crate::ch08::radix_sort_by_digits;
+    pub use crate::ch08::radix_sort_fixed_width;
+    pub use crate::ch08::bucket_sort;
+    pub use crate::ch08::bucket_sort_by_key;
+    pub use crate::ch02::count_inversions;
+    pub use crate::ch02::count_inversions_by;
+    pub use crate::ch02::kendall_tau;
+
+    pub use crate::ch26::p_merge_sort;
+    pub use crate::ch26::p_merge_sort_by;
+}
+
+// Matrix types and matrix multiplication algorithms
+pub mod matrix {
+    pub use crate::utils::matrix::Shape;
+    pub use crate::utils::matrix::Mat;
+    pub use crate::utils::matrix::Vec2d;
+    pub use crate::utils::matrix::Slice2d;
+    pub use crate::utils::matrix::Slice2dMut;
 
     pub use crate::ch04::matrix_multiply;
     pub use crate::ch04::matrix_multiply_recursive;
     pub use crate::ch04::matrix_multiply_strassen;
+}
 
+// Probabilistic analysis and randomized algorithms
+pub mod random {
     pub use crate::ch05::hire_assistant;
     pub use crate::ch05::randomly_permute;
     pub use crate::ch05::randomized_hire_assistant;
     pub use crate::ch05::random_sample;
     pub use crate::ch05::online_maximum;
-}
\ No newline at end of file
+    pub use crate::ch05::online_maximum_aux;
+}
+
+// Medians and order statistics
+pub mod select {
+    pub use crate::ch09::minimum_maximum;
+    pub use crate::ch09::minimum_maximum_by;
+    pub use crate::ch09::randomized_select;
+    pub use crate::ch09::randomized_select_by;
+    pub use crate::ch09::select;
+    pub use crate::ch09::select_by;
+    pub use crate::ch09::nth_smallest;
+    pub use crate::ch09::nth_smallest_by;
+    pub use crate::ch09::quantiles;
+    pub use crate::ch09::quantiles_by;
+    pub use crate::ch09::weighted_median;
+    pub use crate::ch09::post_office_location;
+    pub use crate::ch09::post_office_location_2d;
+}
+
+// Helper traits and functions
+pub mod ops {
+    pub use crate::utils::ops::Len;
+    pub use crate::utils::ops::RangeIndex;
+    pub use crate::utils::ops::IntoRange;
+    pub use crate::utils::ops::Slice;
+    pub use crate::utils::ops::SliceMut;
+    pub use crate::utils::ops::Unsigned;
+
+    pub use crate::utils::is_power_of_two;
+}
+
+// Heaps and priority queues
+pub mod heap {
+    pub use crate::ch06::max_heapify;
+    pub use crate::ch06::max_heapify_by;
+    pub use crate::ch06::build_max_heap;
+    pub use crate::ch06::build_max_heap_by;
+    pub use crate::ch06::Heap;
+    pub use crate::ch06::CompareHeap;
+    pub use crate::ch06::PriorityQueue;
+    pub use crate::ch06::Handle;
+    pub use crate::ch06::DaryHeap;
+    pub use crate::ch06::KWayMerge;
+    pub use crate::ch06::k_way_merge;
+    pub use crate::ch06::k_way_merge_by;
+    pub use crate::ch06::k_way_merge_by_key;
+}
+
+// Elementary data structures
+pub mod container {
+    pub use crate::ch10::ContainerError;
+    pub use crate::ch10::Stack;
+    pub use crate::ch10::Queue;
+    pub use crate::ch10::Deque;
+    pub use crate::ch10::LinkedList;
+    pub use crate::ch10::NodeHandle;
+    pub use crate::ch10::Iter;
+    pub use crate::ch10::ObjectStore;
+    pub use crate::ch10::MultipleArrays;
+    pub use crate::ch10::SingleArray;
+    pub use crate::ch10::ArrayList;
+    pub use crate::ch10::MultipleArrayList;
+    pub use crate::ch10::SingleArrayList;
+}
+
+// Hash tables and hash functions
+pub mod hash {
+    pub use crate::ch11::natural_key;
+    pub use crate::ch11::HashFamily;
+    pub use crate::ch11::HashFunction;
+    pub use crate::ch11::ProbeStats;
+    pub use crate::ch11::ChainedHashTable;
+    pub use crate::ch11::OpenAddressingHashTable;
+    pub use crate::ch11::Probing;
+    pub use crate::ch11::PerfectHashTable;
+    pub use crate::ch11::PerfectHashBuilder;
+    pub use crate::ch11::BuildReport;
+}
+
+// Search trees
+pub mod tree {
+    pub use crate::ch12::BinarySearchTree;
+    pub use crate::ch12::NodeRef;
+    pub use crate::ch12::Walk;
+    pub use crate::ch13::RedBlackTree;
+    pub use crate::ch13::Color;
+    pub use crate::ch13::Iter;
+    pub use crate::ch13::Augment;
+    pub use crate::ch13::PersistentSet;
+    pub use crate::ch13::SetIter;
+    pub use crate::ch17::OrderStatisticTree;
+    pub use crate::ch17::IntervalTree;
+    pub use crate::ch17::Interval;
+    pub use crate::ch18::BTree;
+    pub use crate::ch18::BTreeNode;
+    pub use crate::ch18::PageId;
+    pub use crate::ch18::PageStore;
+    pub use crate::ch18::MemoryPageStore;
+    pub use crate::ch18::FilePageStore;
+    pub use crate::ch18::FixedSize;
+    pub use crate::ch18::IoStats;
+}
+
+// Instrumentation for counting the operations performed by algorithms
+pub mod instrument {
+    pub use crate::utils::counted::Counted;
+    pub use crate::utils::counted::Stats;
+    pub use crate::utils::counted::count_ops;
+    pub use crate::utils::counted::insertion_sort_counted;
+    pub use crate::utils::counted::merge_sort_counted;
+    pub use crate::utils::counted::bubble_sort_counted;
+}
+
+pub mod prelude {
+    pub use crate::sort::insertion_sort;
+    pub use crate::sort::merge_sort;
+    pub use crate::sort::bubble_sort;
+    pub use crate::sort::heapsort;
+    pub use crate::sort::quicksort;
+
+    pub use crate::matrix::matrix_multiply;
+    pub use crate::matrix::matrix_multiply_recursive;
+    pub use crate::matrix::matrix_multiply_strassen;
+
+    pub use crate::random::hire_assistant;
+    pub use crate::random::randomly_permute;
+    pub use crate::random::randomized_hire_assistant;
+    pub use crate::random::random_sample;
+    pub use crate::random::online_maximum;
+}
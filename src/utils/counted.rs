@@ -0,0 +1,257 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use crate::{ch02, utils};
+
+// Numbers of operations performed on `Counted` values. Subtractions count as additions.
+// A move writes an element into a slot of the array or of an auxiliary buffer, so a swap is two moves.
+// Swaps and rotations do not go through the elements, so moves are only counted by the instrumented sorts
+// `insertion_sort_counted`, `merge_sort_counted` and `bubble_sort_counted`, and are 0 for everything else.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub comparisons: u64,
+    pub moves: u64,
+    pub multiplications: u64,
+    pub additions: u64,
+}
+
+thread_local! {
+    static STATS: Cell<Stats> = const { Cell::new(Stats { comparisons: 0, moves: 0, multiplications: 0, additions: 0 }) };
+}
+
+fn record(f: impl FnOnce(&mut Stats)) {
+    STATS.with(|stats| {
+        let mut s = stats.get();
+        f(&mut s);
+        stats.set(s);
+    });
+}
+
+fn record_comparison() {
+    record(|s| s.comparisons += 1);
+}
+
+// Receiver of the moves made by a sort. The plain sorts pass `()`, which compiles the counting away.
+pub(crate) trait MoveCounter {
+    fn record(&mut self, n: usize);
+}
+
+impl MoveCounter for () {
+    fn record(&mut self, _: usize) {}
+}
+
+// Adds the moves to the `Stats` of the current `count_ops`.
+struct RecordMoves;
+
+impl MoveCounter for RecordMoves {
+    fn record(&mut self, n: usize) {
+        record(|s| s.moves += n as u64);
+    }
+}
+
+fn record_multiplication() {
+    record(|s| s.multiplications += 1);
+}
+
+fn record_addition() {
+    record(|s| s.additions += 1);
+}
+
+// Run `f` and return its result along with the operations performed on `Counted` values by `f`.
+// Only operations on the current thread are counted, so the operations of `p_merge_sort` in spawned threads are missed.
+pub fn count_ops<R>(f: impl FnOnce() -> R) -> (R, Stats) {
+    let outer = STATS.replace(Stats::default());
+    let result = f();
+    let inner = STATS.get();
+    STATS.set(Stats {
+        comparisons: outer.comparisons + inner.comparisons,
+        moves: outer.moves + inner.moves,
+        multiplications: outer.multiplications + inner.multiplications,
+        additions: outer.additions + inner.additions,
+    });
+    (result, inner)
+}
+
+// `insertion_sort` that also counts its moves, one for each element of each rotation into place.
+pub fn insertion_sort_counted<T: PartialOrd>(a: &mut [T]) {
+    ch02::insertion_sort_aux(a, &mut utils::partial_compare, &mut RecordMoves);
+}
+
+// `merge_sort` that also counts its moves, one for each element cloned into the buffer and two for each swap back.
+pub fn merge_sort_counted<T: PartialOrd + Clone>(a: &mut [T]) {
+    if a.is_empty() { return; }
+    let mut scratch = Vec::with_capacity(a.len().div_ceil(2));
+    ch02::merge_sort_aux(a, 0, a.len() - 1, 1, &mut scratch, &mut utils::partial_compare, &mut RecordMoves);
+}
+
+// `bubble_sort` that also counts its moves, two for each swap.
+pub fn bubble_sort_counted<T: PartialOrd>(a: &mut [T]) {
+    ch02::bubble_sort_aux(a, &mut utils::partial_compare, &mut RecordMoves);
+}
+
+// Wrapper that counts the comparisons and arithmetic operations performed on `T`,
+// e.g. `insertion_sort(&mut [Counted(3), Counted(1), Counted(2)])` within `count_ops`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Counted<T>(pub T);
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        record_comparison();
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Counted<T> {}
+
+impl<T: PartialOrd> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        record_comparison();
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        record_comparison();
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Mul<Output = T>> Mul for Counted<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        record_multiplication();
+        Counted(self.0 * rhs.0)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Counted<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        record_addition();
+        Counted(self.0 + rhs.0)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Counted<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        record_addition();
+        self.0 += rhs.0;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Counted<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        record_addition();
+        Counted(self.0 - rhs.0)
+    }
+}
+
+impl<T: SubAssign> SubAssign for Counted<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        record_addition();
+        self.0 -= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::seq::SliceRandom;
+    use crate::{ch02, ch04};
+    use crate::utils::matrix::Mat;
+    use super::*;
+
+    fn counted<const N: usize>(a: [i32; N]) -> [Counted<i32>; N] {
+        a.map(Counted)
+    }
+
+    fn mat<const N: usize>(f: impl Fn(usize, usize) -> i32) -> Mat<Counted<i32>, N, N> {
+        Mat(std::array::from_fn(|i| std::array::from_fn(|j| Counted(f(i, j)))))
+    }
+
+    #[test]
+    fn count_ops_test() {
+        let (sum, stats) = count_ops(|| {
+            let mut x = Counted(1) + Counted(2) * Counted(3);
+            x -= Counted(4);
+            x < Counted(5) && x == Counted(3)
+        });
+        assert!(sum);
+        assert_eq!(stats, Stats { comparisons: 2, moves: 0, multiplications: 1, additions: 2 });
+
+        let (_, outer) = count_ops(|| {
+            let (_, inner) = count_ops(|| Counted(1) + Counted(2));
+            assert_eq!(inner.additions, 1);
+            Counted(1) + Counted(2)
+        });
+        assert_eq!(outer.additions, 2);
+    }
+
+    #[test]
+    fn sort_operations() {
+        let n = 64;
+        let reversed: Vec<Counted<i32>> = (0..n).rev().map(Counted).collect();
+        let worst = (n * (n - 1) / 2) as u64;
+
+        // On reversed input, insertion sort rotates i + 1 elements into place for each i, and bubble sort swaps every pair.
+        let (_, stats) = count_ops(|| insertion_sort_counted(&mut reversed.clone()));
+        assert_eq!((stats.comparisons, stats.moves), (worst, worst + n as u64 - 1));
+        let (_, stats) = count_ops(|| bubble_sort_counted(&mut reversed.clone()));
+        assert_eq!((stats.comparisons, stats.moves), (worst, 2 * worst));
+
+        // Merge sort takes at most n⌈lg n⌉ comparisons, and half of them on sorted or reversed input.
+        // Each of the lg n levels clones n/2 elements and swaps at most n elements into place.
+        let mut rng = rand::rng();
+        let mut a = reversed.clone();
+        a.shuffle(&mut rng);
+        let (_, stats) = count_ops(|| merge_sort_counted(&mut a));
+        assert!(stats.comparisons <= (n * 6) as u64, "{stats:?}");
+        assert!(stats.moves <= (n / 2 * 5 * 6) as u64, "{stats:?}");
+        assert!(a.windows(2).all(|w| w[0].0 < w[1].0));
+        let (_, stats) = count_ops(|| merge_sort_counted(&mut reversed.clone()));
+        assert_eq!((stats.comparisons, stats.moves), ((n * 6 / 2) as u64, (n / 2 * 5 * 6) as u64));
+
+        let (_, stats) = count_ops(|| insertion_sort_counted(&mut counted([1, 2, 3, 4])));
+        assert_eq!((stats.comparisons, stats.moves), (3, 0));
+
+        // The plain sorts do not count moves.
+        let (_, stats) = count_ops(|| ch02::insertion_sort(&mut reversed.clone()));
+        assert_eq!((stats.comparisons, stats.moves), (worst, 0));
+        let (_, stats) = count_ops(|| ch02::merge_sort(&mut reversed.clone()));
+        assert_eq!(stats.moves, 0);
+    }
+
+    #[test]
+    fn matrix_multiply_operations() {
+        let a = mat::<8>(|i, j| (i * 8 + j) as i32);
+        let b = mat::<8>(|i, j| (i + j) as i32);
+
+        let (c, stats) = count_ops(|| ch04::matrix_multiply(&a, &b));
+        assert_eq!(stats.multiplications, 8 * 8 * 8);
+        assert_eq!(stats.additions, 8 * 8 * 8);
+
+        let (d, stats) = count_ops(|| ch04::matrix_multiply_recursive(&a, &b));
+        assert_eq!(stats.multiplications, 8 * 8 * 8);
+        assert_eq!(stats.additions, 8 * 8 * 8);
+
+        // Strassen takes 7 rather than 8 multiplications per level of recursion.
+        let (e, stats) = count_ops(|| ch04::matrix_multiply_strassen(&a, &b));
+        assert_eq!(stats.multiplications, 7 * 7 * 7);
+
+        for i in 0..8 {
+            for j in 0..8 {
+                assert_eq!(c[(i, j)].0, d[(i, j)].0);
+                assert_eq!(c[(i, j)].0, e[(i, j)].0);
+            }
+        }
+
+        let a = mat::<2>(|i, j| (i + j) as i32);
+        let (_, stats) = count_ops(|| ch04::matrix_multiply_strassen(&a, &a));
+        // 10 sums S1..S10, 7 products accumulated into zeroed P1..P7, and 12 additions to form C.
+        assert_eq!(stats, Stats { comparisons: 0, moves: 0, multiplications: 7, additions: 10 + 7 + 12 });
+    }
+}
@@ -6,11 +6,11 @@ use crate::utils::ops::{IntoRange, Slice};
 pub struct Mat<T, const M: usize, const N: usize>(pub [[T; N]; M]);
 
 impl<T, const M: usize, const N: usize> Mat<T, M, N> {
-    pub fn as_slice2d(&self) -> Slice2d<[T; N]> {
+    pub fn as_slice2d(&self) -> Slice2d<'_, [T; N]> {
         Slice2d::new(&self.0)
     }
 
-    pub fn as_slice2d_mut(&mut self) -> Slice2dMut<[T; N]> {
+    pub fn as_slice2d_mut(&mut self) -> Slice2dMut<'_, [T; N]> {
         Slice2dMut::new(&mut self.0)
     }
 
@@ -18,7 +18,7 @@ impl<'a, T> Slice2d<'a, T> {
         Slice2d {
             slice,
             row: 0..slice.len(),
-            col: 0..if slice.len() == 0 { 0 } else { slice[0].len() },
+            col: 0..if slice.is_empty() { 0 } else { slice[0].len() },
         }
     }
 
@@ -23,7 +23,7 @@ impl<'a, T> Slice2dMut<'a, T> {
         }
     }
 
-    pub fn as_slice2d(&self) -> Slice2d<T>
+    pub fn as_slice2d(&self) -> Slice2d<'_, T>
     where
         T: Len,
     {
@@ -332,19 +332,19 @@ mod tests {
         assert_eq!(a[(3, 3)], 16);
 
         let mut expected = [[6, 7], [10, 11]];
-        assert_eq!(a.slice((1..3, 1..3)), Slice2d::new(&mut expected));
+        assert_eq!(a.slice((1..3, 1..3)), Slice2d::new(&expected));
         assert_eq!(a.slice_mut((1..3, 1..3)), Slice2dMut::new(&mut expected));
         let mut expected = [[6, 7], [10, 11]];
-        assert_eq!(a.slice((1..=2, 1..=2)), Slice2d::new(&mut expected));
+        assert_eq!(a.slice((1..=2, 1..=2)), Slice2d::new(&expected));
         assert_eq!(a.slice_mut((1..=2, 1..=2)), Slice2dMut::new(&mut expected));
         let mut expected = [[6, 7, 8], [10, 11, 12], [14, 15, 16]];
-        assert_eq!(a.slice((1.., 1..)), Slice2d::new(&mut expected));
+        assert_eq!(a.slice((1.., 1..)), Slice2d::new(&expected));
         assert_eq!(a.slice_mut((1.., 1..)), Slice2dMut::new(&mut expected));
         let mut expected = [[1, 2, 3], [5, 6, 7], [9, 10, 11]];
-        assert_eq!(a.slice((..3, ..3)), Slice2d::new(&mut expected));
+        assert_eq!(a.slice((..3, ..3)), Slice2d::new(&expected));
         assert_eq!(a.slice_mut((..3, ..3)), Slice2dMut::new(&mut expected));
         let mut expected = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]];
-        assert_eq!(a.slice((.., ..)), Slice2d::new(&mut expected));
+        assert_eq!(a.slice((.., ..)), Slice2d::new(&expected));
         assert_eq!(a.slice_mut((.., ..)), Slice2dMut::new(&mut expected));
 
         let mut a = a.slice_mut((1..3, 1..));
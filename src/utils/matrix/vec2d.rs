@@ -21,11 +21,11 @@ impl<T> Vec2d<T> {
         result
     }
 
-    pub fn as_slice2d(&self) -> Slice2d<Vec<T>> {
+    pub fn as_slice2d(&self) -> Slice2d<'_, Vec<T>> {
         Slice2d::new(&self.0)
     }
 
-    pub fn as_slice2d_mut(&mut self) -> Slice2dMut<Vec<T>> {
+    pub fn as_slice2d_mut(&mut self) -> Slice2dMut<'_, Vec<T>> {
         Slice2dMut::new(&mut self.0)
     }
 }
@@ -46,7 +46,7 @@ impl<T> DerefMut for Vec2d<T> {
 
 impl<T> Shape for Vec2d<T> {
     fn shape(&self) -> (usize, usize) {
-        (self.len(), if self.len() == 0 { 0 } else { self.0[0].len() })
+        (self.len(), if self.is_empty() { 0 } else { self.0[0].len() })
     }
 }
 
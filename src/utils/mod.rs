@@ -1,10 +1,21 @@
+use std::cmp::Ordering;
+
 pub mod ops;
 pub mod matrix;
+pub mod counted;
 
 pub fn is_power_of_two(n: usize) -> bool {
     n != 0 && n & (n - 1) == 0
 }
 
+// Comparator of the `_by` variants of the algorithms, when it is stored in a type.
+pub type Compare<T> = fn(&T, &T) -> Ordering;
+
+// Compare `a` and `b` as the `<` and `>` operators do, treating incomparable values (e.g. NaN) as equal.
+pub fn partial_compare<T: PartialOrd + ?Sized>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +40,13 @@ mod tests {
             assert_eq!(is_power_of_two(n), expected);
         }
     }
+
+    #[test]
+    fn partial_compare_test() {
+        assert_eq!(partial_compare(&1, &2), Ordering::Less);
+        assert_eq!(partial_compare(&2, &2), Ordering::Equal);
+        assert_eq!(partial_compare(&3, &2), Ordering::Greater);
+        assert_eq!(partial_compare(&f64::NAN, &2.0), Ordering::Equal);
+        assert_eq!(partial_compare("a", "b"), Ordering::Less);
+    }
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 pub trait Len {
     fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 // Blanket implementation of `Len`
@@ -53,6 +57,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn len() {
         let mut a = [1];
         let mut b = [1, 2];
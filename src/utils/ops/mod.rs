@@ -1,7 +1,9 @@
 mod len;
 mod slice;
 mod range;
+mod unsigned;
 
 pub use len::*;
 pub use slice::*;
-pub use range::*;
\ No newline at end of file
+pub use range::*;
+pub use unsigned::*;
\ No newline at end of file
@@ -0,0 +1,35 @@
+// Unsigned integer types, whose values `counting_sort` uses as keys and `radix_sort` splits into bytes.
+pub trait Unsigned: Copy + Ord {
+    const BITS: u32;
+
+    fn to_u64(self) -> u64;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_test() {
+        assert_eq!(u8::MAX.to_u64(), 255);
+        assert_eq!(u64::MAX.to_u64(), u64::MAX);
+        assert_eq!(<u16 as Unsigned>::BITS, 16);
+        assert_eq!(<usize as Unsigned>::BITS, usize::BITS);
+    }
+}
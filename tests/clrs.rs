@@ -1,5 +1,17 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use clrs::prelude::insertion_sort;
 use clrs::prelude::merge_sort;
+use clrs::sort;
+use clrs::matrix::{self, Mat, Shape, Slice2d, Slice2dMut, Vec2d};
+use clrs::random;
+use clrs::select;
+use clrs::heap::{self, CompareHeap, DaryHeap, Handle, Heap, KWayMerge, PriorityQueue};
+use clrs::container::{ContainerError, Deque, LinkedList, MultipleArrayList, Queue, SingleArrayList, Stack};
+use clrs::hash::{self, ChainedHashTable, HashFamily, HashFunction, OpenAddressingHashTable, PerfectHashBuilder, PerfectHashTable, Probing};
+use clrs::tree::{BTree, BinarySearchTree, FilePageStore, Interval, IntervalTree, IoStats, OrderStatisticTree, PersistentSet, RedBlackTree};
+use clrs::instrument::{self, Counted, Stats};
+use clrs::ops::{self, IntoRange, Len, RangeIndex, Slice, SliceMut, Unsigned};
 
 #[test]
 fn insertion_sort_test() {
@@ -17,4 +29,505 @@ fn merge_sort_test() {
     let mut a = [12, 3, 7, 9, 14, 6, 11, 2];
     merge_sort(&mut a);
     assert_eq!(a, [2, 3, 6, 7, 9, 11, 12, 14]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn sort_test() {
+    let sorts: [fn(&mut [i32]); 9] = [
+        sort::insertion_sort,
+        sort::binary_insertion_sort,
+        sort::merge_sort,
+        sort::bubble_sort,
+        sort::heapsort,
+        sort::quicksort,
+        sort::randomized_quicksort,
+        |a| sort::quicksort_with(a, sort::PartitionScheme::ThreeWay, sort::Pivot::MedianOf3),
+        sort::introsort,
+    ];
+    for sort in sorts {
+        let mut a = [5, 2, 4, 6, 1, 3];
+        sort(&mut a);
+        assert_eq!(a, [1, 2, 3, 4, 5, 6]);
+    }
+
+    let sorts: [fn(&mut [String]); 7] = [
+        sort::insertion_sort,
+        sort::merge_sort,
+        |a| sort::merge_sort_with_buffer(a, &mut Vec::new()),
+        sort::merge_sort_bottom_up,
+        |a| sort::merge_insertion_sort(a, 2),
+        |a| sort::p_merge_sort(a, 2),
+        sort::bubble_sort,
+    ];
+    for sort in sorts {
+        let mut a = ["pear", "apple", "fig", "banana"].map(String::from);
+        sort(&mut a);
+        assert_eq!(a, ["apple", "banana", "fig", "pear"]);
+    }
+}
+
+type SortBy = fn(&mut [i32], fn(&i32, &i32) -> Ordering);
+type SortByKey = fn(&mut [i32], fn(&i32) -> i32);
+
+#[test]
+fn merge_insertion_sort_cutoff_test() {
+    let sample: Vec<i32> = (0..500).rev().collect();
+    let k = sort::merge_insertion_sort_cutoff(&sample);
+    let mut a = sample.clone();
+    sort::merge_insertion_sort(&mut a, k);
+    assert!(a.is_sorted());
+}
+
+#[test]
+fn sort_by_test() {
+    let sorts_by: [SortBy; 13] = [
+        |a, compare| sort::insertion_sort_by(a, compare),
+        |a, compare| sort::binary_insertion_sort_by(a, compare),
+        |a, compare| sort::merge_sort_by(a, compare),
+        |a, compare| sort::merge_sort_with_buffer_by(a, &mut Vec::new(), compare),
+        |a, compare| sort::merge_sort_bottom_up_by(a, compare),
+        |a, compare| sort::merge_insertion_sort_by(a, 2, compare),
+        |a, compare| sort::p_merge_sort_by(a, 1, compare),
+        |a, compare| sort::bubble_sort_by(a, compare),
+        |a, compare| sort::heapsort_by(a, compare),
+        |a, compare| sort::quicksort_by(a, compare),
+        |a, compare| sort::randomized_quicksort_by(a, compare),
+        |a, compare| sort::quicksort_with_by(a, sort::PartitionScheme::Hoare, sort::Pivot::Random, compare),
+        |a, compare| sort::introsort_by(a, compare),
+    ];
+    for sort_by in sorts_by {
+        let mut a = [5, 2, 4, 6, 1, 3];
+        sort_by(&mut a, |x, y| y.cmp(x));
+        assert_eq!(a, [6, 5, 4, 3, 2, 1]);
+    }
+
+    let sorts_by_key: [SortByKey; 9] = [
+        |a, key| sort::insertion_sort_by_key(a, key),
+        |a, key| sort::binary_insertion_sort_by_key(a, key),
+        |a, key| sort::merge_sort_by_key(a, key),
+        |a, key| sort::merge_insertion_sort_by_key(a, 3, key),
+        |a, key| sort::bubble_sort_by_key(a, key),
+        |a, key| sort::heapsort_by_key(a, key),
+        |a, key| sort::quicksort_by_key(a, key),
+        |a, key| sort::randomized_quicksort_by_key(a, key),
+        |a, key| sort::introsort_by_key(a, key),
+    ];
+    for sort_by_key in sorts_by_key {
+        let mut a = [5, -2, 4, -6, 1, -3];
+        sort_by_key(&mut a, |x| x.abs());
+        assert_eq!(a, [1, -2, -3, 4, 5, -6]);
+    }
+}
+
+#[test]
+fn partition_test() {
+    let descending = &mut |x: &i32, y: &i32| y.cmp(x);
+
+    let mut a = [2, 8, 7, 1, 3, 5, 6, 4];
+    assert_eq!(sort::partition(&mut a), 3);
+    let mut a = [2, 8, 7, 1, 3, 5, 6, 4];
+    assert_eq!(sort::partition_by(&mut a, descending), 4);
+
+    let mut a = [13, 19, 9, 5, 12, 8, 7, 4, 11, 2, 6, 21];
+    assert_eq!(sort::hoare_partition(&mut a), 8);
+    let mut a = [3, 1, 2];
+    assert_eq!(sort::hoare_partition_by(&mut a, descending), 0);
+    assert_eq!(a, [3, 1, 2]);
+
+    let mut a = [4, 1, 4, 7, 4, 2, 9, 4];
+    assert_eq!(sort::three_way_partition(&mut a), (2, 6));
+    let mut a = [4, 1, 4, 7, 4, 2, 9, 4];
+    assert_eq!(sort::three_way_partition_by(&mut a, descending), (2, 6));
+}
+
+#[test]
+fn linear_time_sort_test() {
+    let mut a: [u32; 8] = [2, 5, 3, 0, 2, 3, 0, 3];
+    sort::counting_sort(&mut a, 5);
+    assert_eq!(a, [0, 0, 2, 2, 3, 3, 3, 5]);
+
+    let mut a = [(3, 'a'), (1, 'b'), (3, 'c'), (0, 'd')];
+    sort::counting_sort_by_key(&mut a, 3, |&(key, _)| key);
+    assert_eq!(a, [(0, 'd'), (1, 'b'), (3, 'a'), (3, 'c')]);
+
+    let mut a: [u64; 5] = [u64::MAX, 1 << 33, 7, 0, 1 << 8];
+    sort::radix_sort(&mut a);
+    assert_eq!(a, [0, 7, 1 << 8, 1 << 33, u64::MAX]);
+
+    let mut a = [(300u16, 'a'), (2, 'b'), (300, 'c'), (1, 'd')];
+    sort::radix_sort_by_key(&mut a, |&(key, _)| key);
+    assert_eq!(a, [(1, 'd'), (2, 'b'), (300, 'a'), (300, 'c')]);
+
+    let mut a = [329, 457, 657, 839, 436, 720, 355];
+    sort::radix_sort_by_digits(&mut a, 3, 10, |&x, i| x / 10usize.pow(i as u32) % 10);
+    assert_eq!(a, [329, 355, 436, 457, 657, 720, 839]);
+
+    let mut a = ["dog", "cat", "cow", "ant"].map(String::from);
+    sort::radix_sort_fixed_width(&mut a, 3);
+    assert_eq!(a, ["ant", "cat", "cow", "dog"]);
+
+    let mut a = [0.78, 0.17, 0.39, 0.26, 0.72];
+    sort::bucket_sort(&mut a);
+    assert_eq!(a, [0.17, 0.26, 0.39, 0.72, 0.78]);
+
+    let mut a = [78, 17, 39, 26, 72];
+    sort::bucket_sort_by_key(&mut a, |&x| x as f64 / 100.0);
+    assert_eq!(a, [17, 26, 39, 72, 78]);
+}
+
+#[test]
+fn count_inversions_test() {
+    assert_eq!(sort::count_inversions(&[2, 3, 8, 6, 1]), 5);
+    assert_eq!(sort::count_inversions_by(&[2, 3, 8, 6, 1], |x: &i32, y: &i32| y.cmp(x)), 5);
+    assert_eq!(sort::kendall_tau(&[1, 2, 3, 4, 5], &[3, 1, 2, 5, 4]), 0.4);
+}
+
+type MatMul2 = fn(&Mat<i32, 2, 2>, &Mat<i32, 2, 2>) -> Mat<i32, 2, 2>;
+
+#[test]
+fn matrix_multiply_test() {
+    let mat_muls: [MatMul2; 3] = [
+        matrix::matrix_multiply,
+        matrix::matrix_multiply_recursive,
+        matrix::matrix_multiply_strassen,
+    ];
+    for mat_mul in mat_muls {
+        assert_eq!(mat_mul(&Mat([[1, 2], [3, 4]]), &Mat([[5, 6], [7, 8]])), Mat([[19, 22], [43, 50]]));
+    }
+}
+
+#[test]
+fn matrix_types_test() {
+    let mut a = Mat([[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(a.shape(), (2, 3));
+    assert_eq!(a[(1, 2)], 6);
+    assert_eq!(a.to_vec2d(), Vec2d(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+
+    let s: Slice2d<[i32; 3]> = a.slice((.., 1..));
+    assert_eq!(s.shape(), (2, 2));
+    assert_eq!(s, Slice2d::new(&[[2, 3], [5, 6]]));
+    assert_eq!(&s + &s, Vec2d(vec![vec![4, 6], vec![10, 12]]));
+
+    let mut m: Slice2dMut<[i32; 3]> = a.as_slice2d_mut();
+    let mut m = m.slice_mut((..1, ..));
+    m += Mat([[10, 10, 10]]);
+    assert_eq!(m, Slice2dMut::new(&mut [[11, 12, 13]]));
+    assert_eq!(a, Mat([[11, 12, 13], [4, 5, 6]]));
+
+    let mut v = Vec2d::defaults((2, 2));
+    v[(0, 1)] = 1;
+    assert_eq!(v.shape(), (2, 2));
+    assert_eq!(v.as_slice2d().to_vec2d(), Vec2d(vec![vec![0, 1], vec![0, 0]]));
+}
+
+#[test]
+fn random_test() {
+    assert_eq!(random::hire_assistant(&[5, 2, 1, 8, 4, 7, 10, 9, 3, 6]), vec![0, 3, 6]);
+
+    let mut ranks = [1, 2, 3, 4, 5];
+    let hired = random::randomized_hire_assistant(&mut ranks);
+    assert!(!hired.is_empty() && hired[0] == 0);
+    ranks.sort();
+    assert_eq!(ranks, [1, 2, 3, 4, 5]);
+
+    let mut a = [1, 2, 3, 4, 5];
+    random::randomly_permute(&mut a);
+    a.sort();
+    assert_eq!(a, [1, 2, 3, 4, 5]);
+
+    let sample = random::random_sample(3, 10);
+    assert_eq!(sample.len(), 3);
+    assert!(sample.is_subset(&(1..=10).collect::<HashSet<_>>()));
+
+    assert_eq!(random::online_maximum(&[5, 2, 1, 8, 4, 7, 10, 9, 3, 6]), 3);
+    assert_eq!(random::online_maximum_aux(2, &[5, 2, 1, 8, 4, 7, 10, 9, 3, 6]), 3);
+}
+
+#[test]
+fn select_test() {
+    assert_eq!(select::minimum_maximum(&[5, 2, 4, 6, 1, 3]), Some((&1, &6)));
+    assert_eq!(select::minimum_maximum_by(&[5, 2, 4, 6, 1, 3], |x, y| y.cmp(x)), Some((&6, &1)));
+
+    let mut a = [5, 2, 4, 6, 1, 3];
+    assert_eq!(*select::randomized_select(&mut a, 2), 3);
+    assert_eq!(*select::randomized_select_by(&mut a, 2, |x, y| y.cmp(x)), 4);
+    assert_eq!(*select::select(&mut a, 5), 6);
+    assert_eq!(*select::select_by(&mut a, 5, |x, y| y.cmp(x)), 1);
+    assert_eq!(*select::nth_smallest(&mut a, 0), 1);
+    assert_eq!(*select::nth_smallest_by(&mut a, 0, |x, y| y.cmp(x)), 6);
+
+    let mut a: Vec<u32> = (1..=100).collect();
+    assert_eq!(select::quantiles(&mut a, &[0.5, 0.9, 0.99]), [&50, &90, &99]);
+    assert_eq!(select::quantiles_by(&mut a, &[0.5], |x, y| y.cmp(x)), [&51]);
+
+    assert_eq!(*select::weighted_median(&[1, 2, 3, 4, 5], &[0.1, 0.1, 0.1, 0.1, 0.6]), 5);
+    assert_eq!(select::post_office_location(&[0.0, 10.0, 3.0], &[1.0, 1.0, 1.0]), 3.0);
+    assert_eq!(select::post_office_location_2d(&[(0.0, 0.0), (4.0, 1.0), (1.0, 5.0)], &[1.0, 1.0, 1.0]), (1.0, 1.0));
+}
+
+#[test]
+fn ops_test() {
+    fn len<T: Len>(a: T) -> usize {
+        a.len()
+    }
+
+    fn range<R: IntoRange<usize>>(r: R) -> std::ops::Range<usize> {
+        r.into_range(0..10)
+    }
+
+    assert_eq!(len([1, 2, 3]), 3);
+    assert_eq!(len(vec![1, 2]), 2);
+    assert!(Len::is_empty(&Vec::<i32>::new()));
+    assert_eq!(range(3..=7), 3..8);
+    assert_eq!(range(..), 0..10);
+    assert_eq!(RangeIndex::next(&3usize), 4);
+    assert_eq!(<u32 as Unsigned>::BITS, 32);
+    assert_eq!(200u8.to_u64(), 200);
+
+    assert!(ops::is_power_of_two(8));
+    assert!(!ops::is_power_of_two(6));
+}
+
+#[test]
+fn heap_test() {
+    let mut a = [4, 1, 3, 2, 16, 9, 10, 14, 8, 7];
+    heap::build_max_heap(&mut a);
+    assert_eq!(a, [16, 14, 10, 8, 7, 9, 3, 2, 4, 1]);
+    a[0] = 0;
+    heap::max_heapify(&mut a, 0);
+    assert_eq!(a[0], 14);
+
+    let mut a = [4, 1, 3, 2, 16];
+    heap::build_max_heap_by(&mut a, &mut |x: &i32, y: &i32| y.cmp(x));
+    assert_eq!(a[0], 1);
+    a[0] = 20;
+    heap::max_heapify_by(&mut a, 0, &mut |x: &i32, y: &i32| y.cmp(x));
+    assert_eq!(a[0], 2);
+
+    let mut max: CompareHeap<i32> = Heap::max();
+    let mut min = Heap::min();
+    for x in [3, 1, 4, 1, 5] {
+        max.push(x);
+        min.push(x);
+    }
+    assert_eq!((max.len(), max.peek()), (5, Some(&5)));
+    assert_eq!(max.pop(), Some(5));
+    assert_eq!(min.pop(), Some(1));
+    assert_eq!(min.into_sorted_vec(), [5, 4, 3, 1]);
+
+    let heap = Heap::from_vec(vec!["b", "c", "a"], |x: &&str, y: &&str| x.len().cmp(&y.len()).then(x.cmp(y)));
+    assert!(!heap.is_empty());
+    assert_eq!(heap.into_sorted_vec(), ["a", "b", "c"]);
+    let mut heap = Heap::with_compare(|x: &i32, y: &i32| x.cmp(y));
+    heap.push(1);
+    assert_eq!(heap.into_vec(), [1]);
+}
+
+#[test]
+fn priority_queue_test() {
+    let mut q = PriorityQueue::default();
+    let a: Handle = q.insert(3, "a");
+    let b = q.insert(1, "b");
+    let c = q.insert(2, "c");
+    assert_eq!((q.len(), q.maximum()), (3, Some((&3, &"a"))));
+    q.increase_key(b, 4);
+    q.decrease_key(a, 0);
+    assert_eq!(q.get(a), Some((&0, &"a")));
+    assert_eq!(q.delete(c), Some((2, "c")));
+    assert!(!q.contains(c));
+    assert_eq!(q.extract_max(), Some((4, "b")));
+    assert_eq!(q.extract_max(), Some((0, "a")));
+    assert!(q.is_empty());
+    let _: PriorityQueue<i32, ()> = PriorityQueue::new();
+}
+
+#[test]
+fn d_ary_heap_test() {
+    let mut heap: DaryHeap<i32, 4, _> = DaryHeap::max();
+    for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+        heap.push(x);
+    }
+    assert_eq!((heap.len(), heap.peek()), (8, Some(&9)));
+    assert_eq!(heap.pop(), Some(9));
+    assert_eq!(heap.into_sorted_vec(), [1, 1, 2, 3, 4, 5, 6]);
+
+    let mut heap: DaryHeap<i32, 3, _> = DaryHeap::min();
+    heap.push(2);
+    heap.push(1);
+    assert_eq!(heap.pop(), Some(1));
+    assert!(!heap.is_empty());
+
+    let heap = DaryHeap::<_, 3, _>::from_vec(vec![2, 7, 5], |x: &i32, y: &i32| x.cmp(y));
+    assert_eq!(heap.into_vec()[0], 7);
+    let heap: DaryHeap<i32, 2, _> = DaryHeap::with_compare(|x: &i32, y: &i32| y.cmp(x));
+    assert!(heap.is_empty());
+    let _: DaryHeap<i32, 2, _> = DaryHeap::default();
+}
+
+#[test]
+fn k_way_merge_test() {
+    let merge: KWayMerge<_, _> = heap::k_way_merge([vec![1, 4, 7], vec![2, 5], vec![3, 6]]);
+    assert_eq!(merge.collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 7]);
+    let merged: Vec<i32> = heap::k_way_merge_by([vec![7, 4, 1], vec![5, 2]], |x, y| y.cmp(x)).collect();
+    assert_eq!(merged, [7, 5, 4, 2, 1]);
+    let merged: Vec<i32> = heap::k_way_merge_by_key([vec![-1, 3], vec![2, -4]], |x: &i32| x.abs()).collect();
+    assert_eq!(merged, [-1, 2, 3, -4]);
+}
+
+#[test]
+fn container_test() {
+    let mut s = Stack::new(2);
+    s.push(1).unwrap();
+    s.push(2).unwrap();
+    assert_eq!(s.push(3), Err(ContainerError::Overflow));
+    assert_eq!((s.pop(), s.pop(), s.pop()), (Ok(2), Ok(1), Err(ContainerError::Underflow)));
+
+    let mut q = Queue::new(2);
+    q.enqueue('a').unwrap();
+    q.enqueue('b').unwrap();
+    assert_eq!(q.enqueue('c'), Err(ContainerError::Overflow));
+    assert_eq!((q.dequeue(), q.dequeue(), q.dequeue()), (Ok('a'), Ok('b'), Err(ContainerError::Underflow)));
+
+    let mut d = Deque::new(3);
+    d.push_back(2).unwrap();
+    d.push_front(1).unwrap();
+    assert_eq!(d.iter().copied().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(d.pop_back(), Ok(2));
+
+    let mut list = LinkedList::new();
+    let h = list.append("b");
+    list.prepend("a");
+    list.insert_after(h, "c");
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), ["a", "b", "c"]);
+    assert_eq!(list.delete(h), Some("b"));
+    assert_eq!(list.get(h), None);
+    assert_eq!(list.search(&"c").and_then(|h| list.get(h)), Some(&"c"));
+
+    let mut list = MultipleArrayList::new(2);
+    let x = list.insert(1).unwrap();
+    list.insert(2).unwrap();
+    assert_eq!(list.insert(3), Err(ContainerError::Overflow));
+    assert_eq!(list.delete(x), Some(1));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2]);
+
+    let mut list = SingleArrayList::new(2);
+    assert_eq!(list.insert(1), Ok(0));
+    assert_eq!(list.insert(2), Ok(3));
+    assert_eq!(list.search(&1), Some(0));
+}
+
+#[test]
+fn hash_test() {
+    assert_eq!(hash::natural_key(&42u32), 42);
+    assert_eq!(HashFunction::division(701).hash(1000), 299);
+    let h = HashFamily::Universal.function(16, &mut rand::rng());
+    assert!(h.hash(12345) < 16);
+
+    let mut t = ChainedHashTable::new(HashFamily::Multiplication);
+    for k in 0..100 {
+        t.insert(k, k * k);
+    }
+    assert_eq!(t.get(&9), Some(&81));
+    assert_eq!(t.remove(&9), Some(81));
+    assert!(t.load_factor() <= t.max_load_factor());
+    assert!(t.stats().resizes > 0);
+
+    let mut t = OpenAddressingHashTable::new(HashFamily::Division, Probing::Double);
+    for k in 0..100 {
+        t.insert(k.to_string(), k);
+    }
+    assert_eq!(t.get(&String::from("42")), Some(&42));
+    assert_eq!(t.remove(&String::from("42")), Some(42));
+    assert!(!t.contains_key(&String::from("42")));
+    assert!(t.stats().average_probes() >= 1.0);
+
+    let t = PerfectHashTable::new((0..100).map(|k| (k * 7, k)).collect());
+    assert_eq!(t.get(&42), Some(&6));
+    assert_eq!(t.get(&43), None);
+    assert!(t.report().total_slots() <= 400);
+    let t = PerfectHashBuilder::new().space_factor(3.0).build(vec![("a", 1), ("b", 2)]);
+    assert_eq!(t.report().secondary_sizes.len(), 2);
+}
+
+#[test]
+fn tree_test() {
+    let mut t = BinarySearchTree::new();
+    for k in [15, 6, 18, 3, 7, 17, 20, 2, 4, 13, 9] {
+        t.insert(k, k.to_string());
+    }
+    assert_eq!(t.get(&13), Some(&String::from("13")));
+    assert_eq!(t.successor(&13).map(|x| *x.key()), Some(15));
+    assert_eq!(t.delete(&6), Some(String::from("6")));
+    assert_eq!(t.root().and_then(|x| x.left()).map(|x| *x.key()), Some(7));
+    assert!(t.inorder().map(|(k, _)| k).is_sorted());
+    assert_eq!(t.preorder().map(|(&k, _)| k).collect::<Vec<_>>(), [15, 7, 3, 2, 4, 13, 9, 18, 17, 20]);
+
+    let mut t = RedBlackTree::new();
+    for k in 0..100u32 {
+        t.insert(k, k * 2);
+    }
+    t.validate();
+    assert!(t.height().unwrap() <= 12);
+    assert_eq!(t.delete(&50), Some(100));
+    assert_eq!(t.range(48..=52).map(|(&k, _)| k).collect::<Vec<_>>(), [48, 49, 51, 52]);
+    assert_eq!(t.successor(&49), Some((&51, &102)));
+    t.validate();
+
+    let mut t = OrderStatisticTree::new();
+    for k in (0..100).rev() {
+        t.insert(k, ());
+    }
+    assert_eq!(t.os_select(42), Some((&42, &())));
+    t.delete(&10);
+    assert_eq!(t.os_rank(&42), Some(41));
+
+    let mut t = IntervalTree::new();
+    t.interval_insert(Interval::new(9, 17), "standup");
+    t.interval_insert(Interval::from(12..=13), "lunch");
+    t.interval_insert(Interval::new(18, 20), "dinner");
+    let overlaps: Vec<_> = t.interval_search_all(&Interval::new(13, 18)).into_iter().map(|(_, &v)| v).collect();
+    assert_eq!(overlaps, ["standup", "lunch", "dinner"]);
+    assert_eq!(t.interval_delete(&Interval::new(12, 13)), Some("lunch"));
+    assert_eq!(t.interval_search(&Interval::new(12, 12)).map(|(_, &v)| v), Some("standup"));
+
+    let v1: PersistentSet<_> = (0..10).collect();
+    let v2 = v1.insert(10).delete(&0);
+    assert!(v1.iter().copied().eq(0..10));
+    assert!(v2.iter().copied().eq(1..11));
+    assert!(v1.contains(&0) && !v2.contains(&0));
+
+    let mut t = BTree::<u32, u32, 3>::new();
+    for k in 0..1000 {
+        t.insert(k, k + 1);
+    }
+    t.validate();
+    t.reset_stats();
+    assert_eq!(t.search(&1000), None);
+    assert_eq!(t.stats(), IoStats { reads: t.height() as u64, writes: 0 });
+    assert_eq!(t.delete(&500), Some(501));
+
+    let path = std::env::temp_dir().join(format!("clrs-tree-test-{}", std::process::id()));
+    let mut t = BTree::<u32, (), 2, FilePageStore<u32, (), 2>>::with_file(&path).unwrap();
+    for k in (0..100).rev() {
+        t.insert(k, ());
+    }
+    assert!(t.contains_key(&42));
+    assert_eq!(t.entries().len(), 100);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn instrument_test() {
+    let mut a = [5, 2, 4, 6, 1, 3].map(Counted);
+    let (_, stats) = instrument::count_ops(|| instrument::insertion_sort_counted(&mut a));
+    assert_eq!(a.map(|x| x.0), [1, 2, 3, 4, 5, 6]);
+    assert_eq!((stats.comparisons, stats.moves), (12, 13));
+    let (_, stats) = instrument::count_ops(|| sort::insertion_sort(&mut [5, 2, 4, 6, 1, 3].map(Counted)));
+    assert_eq!((stats.comparisons, stats.moves), (12, 0));
+
+    let a = Mat([[Counted(1), Counted(2)], [Counted(3), Counted(4)]]);
+    let (_, stats) = instrument::count_ops(|| matrix::matrix_multiply_strassen(&a, &a));
+    assert_eq!(stats.multiplications, 7);
+    let (_, stats) = instrument::count_ops(|| matrix::matrix_multiply(&a, &a));
+    assert_eq!(stats, Stats { comparisons: 0, moves: 0, multiplications: 8, additions: 8 });
+}